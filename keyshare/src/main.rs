@@ -23,8 +23,8 @@ pub enum Error {
     ChaChaError(#[from] chacha20poly1305::Error),
     #[error("Base64 error: {0}")]
     Base64Error(#[from] base64::DecodeError),
-    #[error("NNShare decode error: {0}")]
-    NnError(#[from] nn_secret_share::Error),
+    #[error("Secret sharing error: {0}")]
+    ShareError(#[from] poly::Error),
     #[error("UTF8 decode error: {0}")]
     Utf8Error(#[from] std::string::FromUtf8Error),
     #[error("IO error: {0}")]
@@ -85,10 +85,10 @@ fn encode(
     let enc_keys;
     if threshold == 0 {
         let t = XorSharer::new(num_shares);
-        enc_keys = t.encode(&key.as_ref()).unwrap();
+        enc_keys = t.encode(&key.as_ref())?;
     } else {
         let t = poly::ShamirSharer::new(num_shares, threshold);
-        enc_keys = t.encode(&key.as_ref()).unwrap();
+        enc_keys = t.encode(&key.as_ref())?;
     }
 
     let nonce = BASE64_STANDARD.encode(nonce);
@@ -121,9 +121,10 @@ fn decode(
                 &BASE64_STANDARD.decode(&b64_keys_list[i].0)?,
             ));
 
-            let t2 = poly::GF2256::new(&new_from_slice(
-                &BASE64_STANDARD.decode(&b64_keys_list[i].1.clone().unwrap())?,
-            ));
+            let y = b64_keys_list[i].1.as_ref().ok_or_else(|| {
+                Error::InvalidArgError("Shamir share is missing its y value".to_string())
+            })?;
+            let t2 = poly::GF2256::new(&new_from_slice(&BASE64_STANDARD.decode(y)?));
 
             keys_list.push(Share::ShamirShare { x: t1, y: t2 });
         }
@@ -135,10 +136,24 @@ fn decode(
     let m;
     if threshold == 0 {
         let t = XorSharer::new(keys_list.len());
-        m = t.decode(&keys_list).unwrap();
+        m = t.decode(&keys_list)?;
     } else {
         let t = ShamirSharer::new(keys_list.len(), threshold);
-        m = t.decode(&keys_list).unwrap();
+        m = t.decode(&keys_list)?;
+    }
+
+    // from_slice panics on the wrong length, which malformed share files can give
+    if m.len() != 32 {
+        return Err(Error::InvalidArgError(format!(
+            "The shares decode to a {} byte key instead of 32",
+            m.len()
+        )));
+    }
+    if nonce.len() != 12 {
+        return Err(Error::InvalidArgError(format!(
+            "The nonce is {} bytes instead of 12",
+            nonce.len()
+        )));
     }
 
     let cipher = ChaCha20Poly1305::new(Key::from_slice(&m));
@@ -228,6 +243,11 @@ fn main() -> Result<(), Error> {
                     for i in 0..num_shares {
                         temp_file = fs::read_to_string(format!("{in_path}/{share_prefix}_{i}"))?;
                         let temp_file: Vec<&str> = temp_file.split(" ").collect();
+                        if temp_file.len() < 3 {
+                            return Err(Error::InvalidArgError(format!(
+                                "Share file {share_prefix}_{i} is not a Shamir share"
+                            )));
+                        }
                         keys_vec
                             .push((String::from(temp_file[1]), Some(String::from(temp_file[2]))));
                     }
@@ -265,4 +285,40 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn malformed_shares_are_errors() -> Result<(), Error> {
+        let (nonce, ciphertext, mut keys_vec) = encode(&String::from("Hello, World!"), 3, 2)?;
+        // the same share twice can't be interpolated
+        let repeated = vec![keys_vec[0].clone(), keys_vec[0].clone()];
+        assert!(decode(&nonce, &ciphertext, &repeated, 2).is_err());
+        keys_vec[1].1 = None;
+        assert!(decode(&nonce, &ciphertext, &keys_vec, 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn wrong_lengths_are_errors() -> Result<(), Error> {
+        let (nonce, ciphertext, mut keys_vec) = encode(&String::from("Hello, World!"), 2, 0)?;
+        let short: Vec<_> = keys_vec
+            .iter()
+            .map(|(share, _)| {
+                (
+                    BASE64_STANDARD.encode(&BASE64_STANDARD.decode(share).unwrap()[..16]),
+                    None,
+                )
+            })
+            .collect();
+        assert!(matches!(
+            decode(&nonce, &ciphertext, &short, 0),
+            Err(Error::InvalidArgError(_))
+        ));
+        let short_nonce = BASE64_STANDARD.encode([0u8; 8]);
+        keys_vec.truncate(2);
+        assert!(matches!(
+            decode(&short_nonce, &ciphertext, &keys_vec, 0),
+            Err(Error::InvalidArgError(_))
+        ));
+        Ok(())
+    }
 }
@@ -1,21 +1,8 @@
 use getrandom;
 use poly::Share;
 
-#[derive(Clone, Debug)]
-pub struct Error;
-
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "noooooooo")?;
-        Ok(())
-    }
-}
-
-impl From<getrandom::Error> for Error {
-    fn from(_err: getrandom::Error) -> Self {
-        Self
-    }
-}
+// SecretSharer fixes the error type, so Xor sharing reports the same cases as Shamir
+pub use poly::Error;
 
 pub struct XorSharer {
     share_count: usize,
@@ -26,13 +13,12 @@ impl XorSharer {
     }
 }
 
-impl std::error::Error for Error {}
 impl poly::SecretSharer for XorSharer {
     fn encode(&self, secret: &[u8]) -> Result<Vec<Share>, poly::Error> {
         let mut vec = Vec::new();
         let mut buf = vec![0; secret.len()];
         if self.share_count == 0 {
-            return Err(poly::Error);
+            return Err(Error::NoShares);
         }
         for _ in 0..self.share_count - 1 {
             getrandom::getrandom(buf.as_mut_slice())?;
@@ -54,20 +40,20 @@ impl poly::SecretSharer for XorSharer {
 
     fn decode(&self, shares: &Vec<Share>) -> Result<Vec<u8>, poly::Error> {
         if shares.len() == 0 {
-            return Err(poly::Error);
+            return Err(Error::NoShares);
         }
         let Share::XorShare(mut t) = shares[0].clone() else {
-            return Err(poly::Error);
+            return Err(Error::WrongShareVariant);
         };
         for i in 1..shares.len() {
             let Share::XorShare(ref si) = shares[i] else {
-                return Err(poly::Error);
+                return Err(Error::WrongShareVariant);
             };
             let Share::XorShare(ref sim1) = shares[i - 1] else {
-                return Err(poly::Error);
+                return Err(Error::WrongShareVariant);
             };
             if si.len() != sim1.len() {
-                return Err(poly::Error);
+                return Err(Error::ShareLengthMismatch);
             }
             for j in 0..si.len() {
                 t[j] ^= si[j];
@@ -135,7 +121,7 @@ mod tests {
         let n = XorSharer::new(4);
         let mut newt = n.encode(&t)?;
         let Share::XorShare(ref mut temp) = newt[0] else {
-            return Err(poly::Error);
+            return Err(Error::WrongShareVariant);
         };
         temp[0] += 15;
         let dect = n.decode(&newt)?;
@@ -145,6 +131,20 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn errors_say_what_went_wrong() -> Result<(), poly::Error> {
+        let n = XorSharer::new(2);
+        assert_eq!(XorSharer::new(0).encode(&[1]).unwrap_err(), Error::NoShares);
+        assert_eq!(n.decode(&vec![]), Err(Error::NoShares));
+        let mut newt = n.encode(&[1, 2])?;
+        newt.push(Share::XorShare(vec![3]));
+        assert_eq!(n.decode(&newt), Err(Error::ShareLengthMismatch));
+        let shamir = poly::ShamirSharer::new(2, 2).encode(&[1])?;
+        assert_eq!(n.decode(&shamir), Err(Error::WrongShareVariant));
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn modified_decode_is_incorrect() {
@@ -0,0 +1,198 @@
+use crate::{FieldElement, Poly};
+use num::rational::Rational64;
+use num::BigUint;
+use std::ops::{Add, Div, Mul, Sub};
+use z2z::Z2z;
+
+// A commutative ring with one, which is all a Poly needs from its coefficients
+pub trait Ring:
+    Clone
+    + PartialEq
+    + num::Zero
+    + num::One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+{
+    // The least n > 0 with 1 + 1 + ... + 1 (n times) = 0, or 0 if there isn't one
+    fn characteristic() -> u64;
+}
+
+// value^exponent by squaring
+pub(crate) fn pow<T: Ring>(value: &T, exponent: usize) -> T {
+    let mut result = T::one();
+    for bit in (0..usize::BITS - exponent.leading_zeros()).rev() {
+        result = result.clone() * result;
+        if (exponent >> bit) & 1 == 1 {
+            result = result * value.clone();
+        }
+    }
+    result
+}
+
+// A ring with division with remainder, so gcds can be found by the euclidean algorithm
+pub trait EuclideanDomain: Ring {
+    // Returns (q, r) with self = q * other + r and r smaller than other.
+    // Panics if other is zero.
+    fn div_rem(&self, other: &Self) -> (Self, Self);
+}
+
+// A ring where every non-zero element has an inverse, so Div is exact
+pub trait Field: Ring + Div<Output = Self> {
+    // The inverse, or None for zero
+    fn checked_inv(&self) -> Option<Self>;
+
+    // The inverse, panicking for zero
+    fn inv(&self) -> Self {
+        match self.checked_inv() {
+            Some(inverse) => inverse,
+            None => panic!("zero has no inverse"),
+        }
+    }
+}
+
+// A field with finitely many elements, which can be sampled and stored as bytes
+pub trait FiniteField: FieldElement {
+    // The number of elements
+    fn order() -> BigUint;
+
+    // x -> x^characteristic, the field automorphism fixing the prime subfield
+    fn frobenius(&self) -> Self;
+}
+
+// Every field is a euclidean domain where division leaves no remainder
+impl<F: Field> EuclideanDomain for F {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        if other.is_zero() {
+            panic!("Division by zero error");
+        }
+        (self.clone() / other.clone(), Self::zero())
+    }
+}
+
+impl Ring for i64 {
+    fn characteristic() -> u64 {
+        0
+    }
+}
+
+// Euclidean division, so the remainder is never negative
+impl EuclideanDomain for i64 {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        (self.div_euclid(*other), self.rem_euclid(*other))
+    }
+}
+
+impl Ring for Rational64 {
+    fn characteristic() -> u64 {
+        0
+    }
+}
+
+impl Field for Rational64 {
+    fn checked_inv(&self) -> Option<Self> {
+        match num::Zero::is_zero(self) {
+            true => None,
+            false => Some(self.recip()),
+        }
+    }
+}
+
+impl Ring for Z2z {
+    fn characteristic() -> u64 {
+        2
+    }
+}
+
+impl Field for Z2z {
+    fn checked_inv(&self) -> Option<Self> {
+        Z2z::checked_inv(self)
+    }
+}
+
+impl FiniteField for Z2z {
+    fn order() -> BigUint {
+        BigUint::from(2u8)
+    }
+
+    // every element of GF(2) is its own square
+    fn frobenius(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Ring> Ring for Poly<T> {
+    fn characteristic() -> u64 {
+        T::characteristic()
+    }
+}
+
+// Polys over a field, with remainders of lower degree
+impl<T: Field> EuclideanDomain for Poly<T> {
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        self.div(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_integer_slice, new_from_slice, GF2256};
+    use num::{One, Zero};
+
+    // The greatest common divisor in any euclidean domain
+    fn gcd<E: EuclideanDomain>(a: E, b: E) -> E {
+        if b.is_zero() {
+            return a;
+        }
+        let remainder = a.div_rem(&b).1;
+        gcd(b, remainder)
+    }
+
+    #[test]
+    fn characteristics() {
+        assert_eq!(i64::characteristic(), 0);
+        assert_eq!(Rational64::characteristic(), 0);
+        assert_eq!(Z2z::characteristic(), 2);
+        assert_eq!(GF2256::characteristic(), 2);
+        assert_eq!(Poly::<Z2z>::characteristic(), 2);
+        assert_eq!(Poly::<Poly<i64>>::characteristic(), 0);
+    }
+
+    #[test]
+    fn euclidean_domains() {
+        assert_eq!(gcd(84i64, 36), 12);
+        assert_eq!((-7i64).div_rem(&2), (-4, 1));
+        assert_eq!(
+            Rational64::new(1, 2).div_rem(&Rational64::new(1, 3)),
+            (Rational64::new(3, 2), Rational64::zero())
+        );
+        // (x - 1)(x + 2) and (x - 1)(x + 3)
+        let a = from_integer_slice(&vec![-2, 1, 1]);
+        let b = from_integer_slice(&vec![-3, 2, 1]);
+        assert_eq!(gcd(a, b).monic(), from_integer_slice(&vec![-1, 1]));
+    }
+
+    #[test]
+    fn inverses() {
+        assert_eq!(Rational64::new(-2, 3).inv(), Rational64::new(-3, 2));
+        assert_eq!(Rational64::zero().checked_inv(), None);
+        assert_eq!(Field::inv(&Z2z::One), Z2z::One);
+        assert_eq!(Field::checked_inv(&Z2z::Zero), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "zero has no inverse")]
+    fn zero_inv_panics() {
+        Rational64::zero().inv();
+    }
+
+    #[test]
+    fn finite_fields() {
+        assert_eq!(<Z2z as FiniteField>::order(), BigUint::from(2u8));
+        assert_eq!(<GF2256 as FiniteField>::order(), BigUint::one() << 256usize);
+        assert_eq!(Z2z::One.frobenius(), Z2z::One);
+        let x = GF2256::new(&new_from_slice(&[0b10]));
+        assert_eq!(x.frobenius(), x.clone() * x);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::Field;
+use crate::Poly;
+
+// Returns the shortest linear recurrence generating the sequence, as (C, L):
+// the connection polynomial C = 1 + c_1 x + ... + c_L x^L and the linear complexity L,
+// with s_n + c_1 s_(n-1) + ... + c_L s_(n-L) = 0 for every n >= L.
+// deg C can be less than L when the recurrence only starts after some leading terms.
+pub fn berlekamp_massey<T: Field>(sequence: &[T]) -> (Poly<T>, usize) {
+    run(sequence, |_complexity| ())
+}
+
+// The linear complexity of each prefix of the sequence, of lengths 1 to sequence.len().
+// A random sequence's profile follows n / 2 closely, so jumps or plateaus are a sign
+// of structure.
+pub fn linear_complexity_profile<T: Field>(sequence: &[T]) -> Vec<usize> {
+    let mut profile = Vec::with_capacity(sequence.len());
+    run(sequence, |complexity| profile.push(complexity));
+    profile
+}
+
+// The Berlekamp-Massey iteration, calling step with the complexity after each term
+fn run<T: Field>(sequence: &[T], mut step: impl FnMut(usize)) -> (Poly<T>, usize) {
+    let mut connection = Poly::new(vec![T::one()]);
+    let mut complexity = 0;
+    // the connection polynomial before the last change of complexity, the discrepancy
+    // that forced the change, and how many terms ago it was
+    let mut previous = Poly::new(vec![T::one()]);
+    let mut previous_discrepancy = T::one();
+    let mut shift = 1;
+    for n in 0..sequence.len() {
+        // how far the current recurrence is from predicting s_n
+        let discrepancy = (1..=complexity).fold(sequence[n].clone(), |sum, i| {
+            sum + connection.coeff(i) * sequence[n - i].clone()
+        });
+        if discrepancy.is_zero() {
+            shift += 1;
+        } else {
+            // subtracting a shifted multiple of previous cancels the discrepancy
+            let scale = discrepancy.clone() / previous_discrepancy.clone();
+            let correction = Poly::monomial(scale, shift) * &previous;
+            if 2 * complexity <= n {
+                let next = &connection - &correction;
+                previous = std::mem::replace(&mut connection, next);
+                complexity = n + 1 - complexity;
+                previous_discrepancy = discrepancy;
+                shift = 1;
+            } else {
+                connection -= &correction;
+                shift += 1;
+            }
+        }
+        step(complexity);
+    }
+    (connection, complexity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_integer_slice, new_from_slice};
+    use num::rational::Rational64;
+    use num::{One, Zero};
+    use z2z::Z2z;
+
+    fn bits(values: &[u8]) -> Vec<Z2z> {
+        values.iter().map(|x| Z2z::from(*x == 1)).collect()
+    }
+
+    // The recurrence holds for every term it covers
+    fn generates<T: Field>(sequence: &[T], connection: &Poly<T>, complexity: usize) -> bool {
+        (complexity..sequence.len()).all(|n| {
+            (0..=complexity)
+                .fold(T::zero(), |sum, i| {
+                    sum + connection.coeff(i) * sequence[n - i].clone()
+                })
+                .is_zero()
+        })
+    }
+
+    #[test]
+    fn fibonacci() {
+        let sequence: Vec<Rational64> = [1, 1, 2, 3, 5, 8, 13, 21]
+            .map(Rational64::from_integer)
+            .to_vec();
+        let (connection, complexity) = berlekamp_massey(&sequence);
+        assert_eq!(connection, from_integer_slice(&vec![1, -1, -1]));
+        assert_eq!(complexity, 2);
+    }
+
+    #[test]
+    fn lfsr_bitstream() {
+        // s_n = s_(n-3) + s_(n-4), from the primitive x^4 + x^3 + 1, has period 15
+        let mut sequence = bits(&[1, 0, 0, 0]);
+        for n in 4..30 {
+            sequence.push(sequence[n - 3] + sequence[n - 4]);
+        }
+        let (connection, complexity) = berlekamp_massey(&sequence);
+        assert_eq!(complexity, 4);
+        assert_eq!(connection, new_from_slice(&[0b11001]));
+        assert!(generates(&sequence, &connection, complexity));
+    }
+
+    #[test]
+    fn degenerate_sequences() {
+        let (connection, complexity) = berlekamp_massey::<Z2z>(&[]);
+        assert!(connection.is_one() && complexity == 0);
+        let (connection, complexity) = berlekamp_massey(&bits(&[0, 0, 0]));
+        assert!(connection.is_one() && complexity == 0);
+        // an impulse at the end needs the whole sequence as its state
+        let sequence = bits(&[0, 0, 0, 1]);
+        let (connection, complexity) = berlekamp_massey(&sequence);
+        assert_eq!(complexity, 4);
+        assert!(generates(&sequence, &connection, complexity));
+        // an impulse at the start is followed by zeros, so C = 1 with L = 1
+        let (connection, complexity) = berlekamp_massey(&bits(&[1, 0, 0, 0]));
+        assert!(connection.is_one() && complexity == 1);
+    }
+
+    #[test]
+    fn matches_the_series_of_a_rational_function() {
+        // a sequence is linearly recurrent exactly when its generating function is
+        // P / C with deg P < L, so S * C mod x^n has degree below L
+        let sequence: Vec<Rational64> = [2, -1, 3, 0, 5, -7, 1, 4, 4, -2]
+            .map(Rational64::from_integer)
+            .to_vec();
+        let (connection, complexity) = berlekamp_massey(&sequence);
+        assert!(generates(&sequence, &connection, complexity));
+        let series = Poly::new(sequence.clone()) * connection;
+        let numerator: Vec<Rational64> = series.coeffs().take(sequence.len()).cloned().collect();
+        assert!(Poly::new(numerator).degree().is_none_or(|d| d < complexity));
+        // generic sequences need half their length
+        assert_eq!(complexity, 5);
+    }
+
+    #[test]
+    fn profile() {
+        assert_eq!(
+            linear_complexity_profile(&bits(&[1, 0, 0, 0])),
+            vec![1, 1, 1, 1]
+        );
+        assert_eq!(
+            linear_complexity_profile(&bits(&[0, 0, 0, 1, 1])),
+            vec![0, 0, 0, 4, 4]
+        );
+        let mut sequence = bits(&[1, 0, 0, 0]);
+        for n in 4..20 {
+            sequence.push(sequence[n - 3] + sequence[n - 4]);
+        }
+        let profile = linear_complexity_profile(&sequence);
+        assert_eq!(profile.len(), 20);
+        assert_eq!(profile[19], berlekamp_massey(&sequence).1);
+        assert!(profile.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(profile[7..].iter().all(|complexity| *complexity == 4));
+    }
+
+    #[test]
+    fn zero_sequence_has_zero_profile() {
+        let sequence = vec![Rational64::zero(); 5];
+        assert_eq!(linear_complexity_profile(&sequence), vec![0; 5]);
+    }
+}
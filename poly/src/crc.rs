@@ -0,0 +1,518 @@
+use crate::Poly;
+use z2z::Z2z;
+
+// The parameters of a CRC in the Rocksoft model: the generator of degree width,
+// written without its x^width term with x^(width-1) in the top bit, the starting
+// register, whether bytes go in (and the CRC comes out) lowest bit first, and a final xor.
+// Built like PolyFormatter, starting from a generator with everything else off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CrcParams {
+    width: u32,
+    poly: u64,
+    init: u64,
+    reflect_in: bool,
+    reflect_out: bool,
+    xorout: u64,
+}
+
+impl CrcParams {
+    pub const CRC_8_SMBUS: CrcParams = CrcParams::raw(8, 0x07, 0x00, false, false, 0x00);
+    pub const CRC_8_MAXIM_DOW: CrcParams = CrcParams::raw(8, 0x31, 0x00, true, true, 0x00);
+    pub const CRC_8_AUTOSAR: CrcParams = CrcParams::raw(8, 0x2f, 0xff, false, false, 0xff);
+    pub const CRC_8_CDMA2000: CrcParams = CrcParams::raw(8, 0x9b, 0xff, false, false, 0x00);
+    pub const CRC_16_ARC: CrcParams = CrcParams::raw(16, 0x8005, 0x0000, true, true, 0x0000);
+    pub const CRC_16_IBM_3740: CrcParams = CrcParams::raw(16, 0x1021, 0xffff, false, false, 0x0000);
+    pub const CRC_16_KERMIT: CrcParams = CrcParams::raw(16, 0x1021, 0x0000, true, true, 0x0000);
+    pub const CRC_16_XMODEM: CrcParams = CrcParams::raw(16, 0x1021, 0x0000, false, false, 0x0000);
+    pub const CRC_16_MODBUS: CrcParams = CrcParams::raw(16, 0x8005, 0xffff, true, true, 0x0000);
+    pub const CRC_16_IBM_SDLC: CrcParams = CrcParams::raw(16, 0x1021, 0xffff, true, true, 0xffff);
+    pub const CRC_32_ISO_HDLC: CrcParams =
+        CrcParams::raw(32, 0x04c11db7, 0xffffffff, true, true, 0xffffffff);
+    pub const CRC_32_ISCSI: CrcParams =
+        CrcParams::raw(32, 0x1edc6f41, 0xffffffff, true, true, 0xffffffff);
+    pub const CRC_32_BZIP2: CrcParams =
+        CrcParams::raw(32, 0x04c11db7, 0xffffffff, false, false, 0xffffffff);
+    pub const CRC_32_MPEG_2: CrcParams =
+        CrcParams::raw(32, 0x04c11db7, 0xffffffff, false, false, 0x00000000);
+    pub const CRC_32_CKSUM: CrcParams =
+        CrcParams::raw(32, 0x04c11db7, 0x00000000, false, false, 0xffffffff);
+    pub const CRC_64_ECMA_182: CrcParams =
+        CrcParams::raw(64, 0x42f0e1eba9ea3693, 0, false, false, 0);
+    pub const CRC_64_XZ: CrcParams =
+        CrcParams::raw(64, 0x42f0e1eba9ea3693, u64::MAX, true, true, u64::MAX);
+    pub const CRC_64_GO_ISO: CrcParams = CrcParams::raw(64, 0x1b, u64::MAX, true, true, u64::MAX);
+
+    const fn raw(
+        width: u32,
+        poly: u64,
+        init: u64,
+        reflect_in: bool,
+        reflect_out: bool,
+        xorout: u64,
+    ) -> Self {
+        CrcParams {
+            width,
+            poly,
+            init,
+            reflect_in,
+            reflect_out,
+            xorout,
+        }
+    }
+
+    // The CRC that is the remainder mod generator, with init and xorout zero and
+    // nothing reflected. Panics unless the generator has degree 1 to 64.
+    pub fn new(generator: &Poly<Z2z>) -> Self {
+        let width = match generator.degree() {
+            Some(width @ 1..=64) => width as u32,
+            _ => panic!("CRC generators must have degree 1 to 64"),
+        };
+        CrcParams::raw(width, bits_from_poly(generator, width), 0, false, false, 0)
+    }
+
+    // The register before any bytes, as it would be without reflection
+    pub fn init(mut self, init: u64) -> Self {
+        self.init = init & self.mask();
+        self
+    }
+
+    // Feeds each byte in lowest bit first
+    pub fn reflect_in(mut self, reflect_in: bool) -> Self {
+        self.reflect_in = reflect_in;
+        self
+    }
+
+    // Reverses the width bits of the register at the end, before xorout
+    pub fn reflect_out(mut self, reflect_out: bool) -> Self {
+        self.reflect_out = reflect_out;
+        self
+    }
+
+    pub fn xorout(mut self, xorout: u64) -> Self {
+        self.xorout = xorout & self.mask();
+        self
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    // x^width plus the bits of poly
+    pub fn generator(&self) -> Poly<Z2z> {
+        poly_from_bits(self.poly, self.width) + Poly::monomial(Z2z::One, self.width as usize)
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
+    }
+}
+
+// A catalogue entry, with the published check value: the CRC of b"123456789"
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrcPreset {
+    pub name: &'static str,
+    pub params: CrcParams,
+    pub check: u64,
+}
+
+impl CrcPreset {
+    // Looks a preset up by name, ignoring case
+    pub fn find(name: &str) -> Option<&'static CrcPreset> {
+        CRC_PRESETS
+            .iter()
+            .find(|preset| preset.name.eq_ignore_ascii_case(name))
+    }
+}
+
+// Presets named as in the CRC RevEng catalogue
+pub const CRC_PRESETS: &[CrcPreset] = &[
+    CrcPreset {
+        name: "CRC-8/SMBUS",
+        params: CrcParams::CRC_8_SMBUS,
+        check: 0xf4,
+    },
+    CrcPreset {
+        name: "CRC-8/MAXIM-DOW",
+        params: CrcParams::CRC_8_MAXIM_DOW,
+        check: 0xa1,
+    },
+    CrcPreset {
+        name: "CRC-8/AUTOSAR",
+        params: CrcParams::CRC_8_AUTOSAR,
+        check: 0xdf,
+    },
+    CrcPreset {
+        name: "CRC-8/CDMA2000",
+        params: CrcParams::CRC_8_CDMA2000,
+        check: 0xda,
+    },
+    CrcPreset {
+        name: "CRC-16/ARC",
+        params: CrcParams::CRC_16_ARC,
+        check: 0xbb3d,
+    },
+    CrcPreset {
+        name: "CRC-16/IBM-3740",
+        params: CrcParams::CRC_16_IBM_3740,
+        check: 0x29b1,
+    },
+    CrcPreset {
+        name: "CRC-16/KERMIT",
+        params: CrcParams::CRC_16_KERMIT,
+        check: 0x2189,
+    },
+    CrcPreset {
+        name: "CRC-16/XMODEM",
+        params: CrcParams::CRC_16_XMODEM,
+        check: 0x31c3,
+    },
+    CrcPreset {
+        name: "CRC-16/MODBUS",
+        params: CrcParams::CRC_16_MODBUS,
+        check: 0x4b37,
+    },
+    CrcPreset {
+        name: "CRC-16/IBM-SDLC",
+        params: CrcParams::CRC_16_IBM_SDLC,
+        check: 0x906e,
+    },
+    CrcPreset {
+        name: "CRC-32/ISO-HDLC",
+        params: CrcParams::CRC_32_ISO_HDLC,
+        check: 0xcbf43926,
+    },
+    CrcPreset {
+        name: "CRC-32/ISCSI",
+        params: CrcParams::CRC_32_ISCSI,
+        check: 0xe3069283,
+    },
+    CrcPreset {
+        name: "CRC-32/BZIP2",
+        params: CrcParams::CRC_32_BZIP2,
+        check: 0xfc891918,
+    },
+    CrcPreset {
+        name: "CRC-32/MPEG-2",
+        params: CrcParams::CRC_32_MPEG_2,
+        check: 0x0376e6e7,
+    },
+    CrcPreset {
+        name: "CRC-32/CKSUM",
+        params: CrcParams::CRC_32_CKSUM,
+        check: 0x765e7680,
+    },
+    CrcPreset {
+        name: "CRC-64/ECMA-182",
+        params: CrcParams::CRC_64_ECMA_182,
+        check: 0x6c40df5f0b497347,
+    },
+    CrcPreset {
+        name: "CRC-64/XZ",
+        params: CrcParams::CRC_64_XZ,
+        check: 0x995dc9bbdf1939fa,
+    },
+    CrcPreset {
+        name: "CRC-64/GO-ISO",
+        params: CrcParams::CRC_64_GO_ISO,
+        check: 0xb90956c775a41001,
+    },
+];
+
+// A table-driven CRC. The register is kept in the top width bits of a u64, or
+// reflected into the bottom width bits when bytes go in lowest bit first, so one
+// set of tables serves every width. tables[k][b] is the register after the byte b
+// followed by k zero bytes, which lets 8 bytes at a time go through (slice-by-8).
+#[derive(Clone)]
+pub struct Crc {
+    params: CrcParams,
+    tables: Box<[[u64; 256]; 8]>,
+}
+
+impl Crc {
+    pub fn new(params: CrcParams) -> Self {
+        let reflected = params.reflect_in;
+        let poly = match reflected {
+            true => reflect(params.poly, params.width),
+            false => params.poly << (64 - params.width),
+        };
+        let mut tables = Box::new([[0; 256]; 8]);
+        for (byte, entry) in tables[0].iter_mut().enumerate() {
+            // one bit at a time, subtracting the generator whenever x^width comes out
+            let mut register = match reflected {
+                true => byte as u64,
+                false => (byte as u64) << 56,
+            };
+            for _ in 0..8 {
+                register = match (reflected, register & 1 == 1, register >> 63 == 1) {
+                    (true, true, _) => (register >> 1) ^ poly,
+                    (true, false, _) => register >> 1,
+                    (false, _, true) => (register << 1) ^ poly,
+                    (false, _, false) => register << 1,
+                };
+            }
+            *entry = register;
+        }
+        for k in 1..8 {
+            for byte in 0..256 {
+                let previous = tables[k - 1][byte];
+                tables[k][byte] = match reflected {
+                    true => (previous >> 8) ^ tables[0][(previous & 0xff) as usize],
+                    false => (previous << 8) ^ tables[0][(previous >> 56) as usize],
+                };
+            }
+        }
+        Crc { params, tables }
+    }
+
+    pub fn params(&self) -> &CrcParams {
+        &self.params
+    }
+
+    pub fn checksum(&self, bytes: &[u8]) -> u64 {
+        let mut digest = self.digest();
+        digest.update(bytes);
+        digest.finalize()
+    }
+
+    // For the CRC of bytes that come in pieces
+    pub fn digest(&self) -> CrcDigest<'_> {
+        let params = &self.params;
+        let register = match params.reflect_in {
+            true => reflect(params.init, params.width),
+            false => params.init << (64 - params.width),
+        };
+        CrcDigest {
+            crc: self,
+            register,
+        }
+    }
+
+    // Eight bytes per round through the tables, then one at a time for the rest
+    fn update(&self, mut register: u64, bytes: &[u8]) -> u64 {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            // the register lines up with the next 8 bytes, in the order they go in
+            let word: [u8; 8] = chunk.try_into().unwrap();
+            let word = match self.params.reflect_in {
+                true => (register ^ u64::from_le_bytes(word)).to_le_bytes(),
+                false => (register ^ u64::from_be_bytes(word)).to_be_bytes(),
+            };
+            register = word
+                .iter()
+                .enumerate()
+                .fold(0, |sum, (i, byte)| sum ^ self.tables[7 - i][*byte as usize]);
+        }
+        self.update_bytewise(register, chunks.remainder())
+    }
+
+    fn update_bytewise(&self, mut register: u64, bytes: &[u8]) -> u64 {
+        for byte in bytes {
+            let byte = *byte as u64;
+            register = match self.params.reflect_in {
+                true => (register >> 8) ^ self.tables[0][((register ^ byte) & 0xff) as usize],
+                false => (register << 8) ^ self.tables[0][((register >> 56) ^ byte) as usize],
+            };
+        }
+        register
+    }
+
+    fn finalize(&self, register: u64) -> u64 {
+        let params = &self.params;
+        let mut crc = match params.reflect_in {
+            true => register,
+            false => register >> (64 - params.width),
+        };
+        if params.reflect_in != params.reflect_out {
+            crc = reflect(crc, params.width);
+        }
+        crc ^ params.xorout
+    }
+}
+
+// The tables are left out
+impl std::fmt::Debug for Crc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.debug_struct("Crc")
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+// A CRC part way through some bytes
+#[derive(Clone, Debug)]
+pub struct CrcDigest<'a> {
+    crc: &'a Crc,
+    register: u64,
+}
+
+impl CrcDigest<'_> {
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.register = self.crc.update(self.register, bytes);
+    }
+
+    // The CRC of the bytes so far, which more updates can follow
+    pub fn finalize(&self) -> u64 {
+        self.crc.finalize(self.register)
+    }
+}
+
+// Reverses the bottom width bits
+fn reflect(value: u64, width: u32) -> u64 {
+    value.reverse_bits() >> (64 - width)
+}
+
+// The coefficients of x^0 to x^(width-1) as the bits of a u64
+fn bits_from_poly(poly: &Poly<Z2z>, width: u32) -> u64 {
+    (0..width).fold(0, |bits, i| match poly.coeff(i as usize) {
+        Z2z::One => bits | (1 << i),
+        Z2z::Zero => bits,
+    })
+}
+
+fn poly_from_bits(bits: u64, width: u32) -> Poly<Z2z> {
+    Poly::new(
+        (0..width)
+            .map(|i| Z2z::from((bits >> i) & 1 == 1))
+            .collect(),
+    )
+}
+
+impl From<CrcParams> for Crc {
+    fn from(params: CrcParams) -> Self {
+        Crc::new(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::new_from_hex;
+    use proptest::prelude::*;
+
+    // The CRC straight from its definition: with the message read as a Poly, first bit
+    // highest, the register ends as init x^(bits) + message x^width mod the generator
+    fn crc_by_division(params: &CrcParams, bytes: &[u8]) -> u64 {
+        let mut message = Vec::new();
+        for byte in bytes.iter().rev() {
+            for i in 0..8 {
+                let bit = match params.reflect_in {
+                    true => 7 - i,
+                    false => i,
+                };
+                message.push(Z2z::from((byte >> bit) & 1 == 1));
+            }
+        }
+        let init = poly_from_bits(params.init, params.width);
+        let register = (init * Poly::monomial(Z2z::One, 8 * bytes.len())
+            + Poly::new(message) * Poly::monomial(Z2z::One, params.width as usize))
+        .modulus(&params.generator());
+        let crc = bits_from_poly(&register, params.width);
+        match params.reflect_out {
+            true => reflect(crc, params.width) ^ params.xorout,
+            false => crc ^ params.xorout,
+        }
+    }
+
+    #[test]
+    fn catalogue_check_values() {
+        for preset in CRC_PRESETS {
+            let crc = Crc::new(preset.params);
+            assert_eq!(crc.checksum(b"123456789"), preset.check, "{}", preset.name);
+        }
+        assert_eq!(
+            CrcPreset::find("crc-32/iso-hdlc").map(|preset| preset.check),
+            Some(0xcbf43926)
+        );
+        assert!(CrcPreset::find("CRC-7/NONE").is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn tables_match_long_division(message in proptest::collection::vec(any::<u8>(), 0..32)) {
+            for preset in CRC_PRESETS {
+                let crc = Crc::new(preset.params);
+                prop_assert_eq!(
+                    crc.checksum(&message),
+                    crc_by_division(&preset.params, &message),
+                    "{}",
+                    preset.name
+                );
+            }
+        }
+
+        #[test]
+        fn slice_by_8_matches_bytewise(
+            message in proptest::collection::vec(any::<u8>(), 0..100),
+            init in any::<u64>(),
+        ) {
+            // one reflected and one unreflected, each from its own starting register
+            for params in [CrcParams::CRC_64_XZ, CrcParams::CRC_16_XMODEM] {
+                let crc = Crc::new(params.init(init));
+                let register = crc.digest().register;
+                prop_assert_eq!(
+                    crc.update(register, &message),
+                    crc.update_bytewise(register, &message)
+                );
+            }
+        }
+
+        #[test]
+        fn digests_take_pieces(
+            message in proptest::collection::vec(any::<u8>(), 0..50),
+            piece in 1..20usize,
+        ) {
+            let crc = Crc::new(CrcParams::CRC_32_ISCSI);
+            let mut digest = crc.digest();
+            for piece in message.chunks(piece) {
+                digest.update(piece);
+            }
+            prop_assert_eq!(digest.finalize(), crc.checksum(&message));
+        }
+    }
+
+    #[test]
+    fn from_a_generator() {
+        // CRC-5/USB has a width under a byte: x^5 + x^2 + 1
+        let params = CrcParams::new(&new_from_hex("25").unwrap())
+            .init(0x1f)
+            .reflect_in(true)
+            .reflect_out(true)
+            .xorout(0x1f);
+        assert_eq!(params.width(), 5);
+        assert_eq!(Crc::new(params).checksum(b"123456789"), 0x19);
+        assert_eq!(
+            CrcParams::CRC_32_ISO_HDLC.generator(),
+            new_from_hex("104c11db7").unwrap()
+        );
+        // CRC-3/GSM, reading the bits the other way from how they go in
+        let params = CrcParams::new(&new_from_hex("b").unwrap()).xorout(0x7);
+        assert_eq!(Crc::from(params).checksum(b"123456789"), 0x4);
+        let params = params.reflect_out(true);
+        assert_eq!(
+            Crc::new(params).checksum(b"hello"),
+            crc_by_division(&params, b"hello")
+        );
+    }
+
+    #[test]
+    fn plain_crc_is_the_remainder() {
+        // with nothing else set, appending the CRC leaves a multiple of the generator
+        let crc = Crc::new(CrcParams::CRC_16_XMODEM);
+        let mut message = b"polynomials".to_vec();
+        let check = crc.checksum(&message);
+        message.extend_from_slice(&(check as u16).to_be_bytes());
+        assert_eq!(crc.checksum(&message), 0);
+    }
+
+    #[test]
+    fn empty_digest() {
+        let crc = Crc::new(CrcParams::CRC_32_ISCSI);
+        assert_eq!(crc.digest().finalize(), crc.checksum(&[]));
+    }
+
+    #[test]
+    #[should_panic(expected = "degree 1 to 64")]
+    fn generators_need_a_width() {
+        CrcParams::new(&new_from_hex("1").unwrap());
+    }
+}
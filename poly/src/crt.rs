@@ -0,0 +1,192 @@
+use crate::euclidean;
+use crate::Field;
+use crate::Poly;
+use num::{CheckedMul, Integer, Signed, Zero};
+
+// Returns the unique Poly x with deg x < deg(m_1 * ... * m_k) and
+// x = residues[i] (mod moduli[i]) for every i.
+// None if the lengths differ, a modulus is zero or the moduli are not pairwise coprime.
+pub fn crt<T: Field>(residues: &[Poly<T>], moduli: &[Poly<T>]) -> Option<Poly<T>> {
+    if residues.len() != moduli.len() || moduli.iter().any(|modulus| modulus.is_zero()) {
+        return None;
+    }
+    if residues.is_empty() {
+        return Some(Poly::new(vec![T::zero()]));
+    }
+    Some(crt_tree(residues, moduli)?.0)
+}
+
+// Solves each half of the system, then merges the two answers. Splitting in half keeps
+// the two products being merged about the same size, but each merge still works on the
+// whole products, so this is divide and conquer, not a fast subproduct tree.
+fn crt_tree<T: Field>(residues: &[Poly<T>], moduli: &[Poly<T>]) -> Option<(Poly<T>, Poly<T>)> {
+    if residues.len() == 1 {
+        return Some((residues[0].modulus(&moduli[0]), moduli[0].clone()));
+    }
+    let half = residues.len() / 2;
+    let (x1, m1) = crt_tree(&residues[..half], &moduli[..half])?;
+    let (x2, m2) = crt_tree(&residues[half..], &moduli[half..])?;
+    // s * m1 + t * m2 = gcd, which is 1 when they're coprime, and then s is the
+    // inverse of m1 mod m2
+    let (s, _t, gcd) = euclidean(&m1, &m2);
+    if gcd.degree() != Some(0) {
+        return None;
+    }
+    let lift = ((x2 - &x1) * s) % &m2;
+    Some((x1 + &(&m1 * &lift), m1 * m2))
+}
+
+// Returns the unique x with 0 <= x < m_1 * ... * m_k and
+// x = residues[i] (mod moduli[i]) for every i.
+// None if the lengths differ, a modulus isn't positive, the moduli are not
+// pairwise coprime, or a product overflows I (which BigInt never does).
+// Signed, since the lift subtracts residues and extended_gcd gives negative coefficients.
+pub fn crt_integer<I: Integer + Signed + CheckedMul + Clone>(
+    residues: &[I],
+    moduli: &[I],
+) -> Option<I> {
+    if residues.len() != moduli.len() || moduli.iter().any(|modulus| !modulus.is_positive()) {
+        return None;
+    }
+    if residues.is_empty() {
+        return Some(I::zero());
+    }
+    Some(crt_integer_tree(residues, moduli)?.0)
+}
+
+fn crt_integer_tree<I: Integer + Signed + CheckedMul + Clone>(
+    residues: &[I],
+    moduli: &[I],
+) -> Option<(I, I)> {
+    if residues.len() == 1 {
+        return Some((residues[0].mod_floor(&moduli[0]), moduli[0].clone()));
+    }
+    let half = residues.len() / 2;
+    let (x1, m1) = crt_integer_tree(&residues[..half], &moduli[..half])?;
+    let (x2, m2) = crt_integer_tree(&residues[half..], &moduli[half..])?;
+    let gcd = m1.extended_gcd(&m2);
+    if !gcd.gcd.is_one() {
+        return None;
+    }
+    let lift = (x2 - x1.clone()).checked_mul(&gcd.x)?.mod_floor(&m2);
+    // x1 < m1 and lift < m2, so the sum is below m1 * m2 and can't overflow
+    Some((x1 + m1.checked_mul(&lift)?, m1.checked_mul(&m2)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_integer_slice, interpolate, new_from_slice};
+    use num::rational::Rational64;
+    use num::BigInt;
+
+    #[test]
+    fn crt_linear_moduli_matches_interpolate() {
+        let xs = [1, 2, 3, 5, 8];
+        let ys = [4, -1, 0, 7, 2];
+        let residues: Vec<_> = ys.iter().map(|&y| from_integer_slice(&vec![y])).collect();
+        let moduli: Vec<_> = xs
+            .iter()
+            .map(|&x| from_integer_slice(&vec![-x, 1]))
+            .collect();
+        let xs: Vec<_> = xs.iter().map(|&x| Rational64::from_integer(x)).collect();
+        let ys: Vec<_> = ys.iter().map(|&y| Rational64::from_integer(y)).collect();
+        assert_eq!(
+            crt(&residues, &moduli),
+            Some(interpolate(&xs, &ys).unwrap())
+        );
+    }
+
+    #[test]
+    fn crt_z2z_residues() {
+        // x, x + 1, x^2 + x + 1 and x^3 + x + 1 are irreducible over Z2
+        let moduli = vec![
+            new_from_slice(&[0b10]),
+            new_from_slice(&[0b11]),
+            new_from_slice(&[0b111]),
+            new_from_slice(&[0b1011]),
+        ];
+        let residues = vec![
+            new_from_slice(&[1]),
+            new_from_slice(&[0]),
+            new_from_slice(&[0b10]),
+            new_from_slice(&[0b110]),
+        ];
+        let x = crt(&residues, &moduli).unwrap();
+        assert!(x.degree().is_some_and(|d| d < 7));
+        for (residue, modulus) in residues.iter().zip(moduli.iter()) {
+            assert_eq!(x.modulus(modulus), *residue);
+        }
+    }
+
+    #[test]
+    fn crt_not_coprime() {
+        let moduli = vec![new_from_slice(&[0b10]), new_from_slice(&[0b100])];
+        let residues = vec![new_from_slice(&[1]), new_from_slice(&[1])];
+        assert_eq!(crt(&residues, &moduli), None);
+    }
+
+    #[test]
+    fn crt_mismatched_lengths() {
+        let moduli = vec![new_from_slice(&[0b10]), new_from_slice(&[0b11])];
+        let residues = vec![new_from_slice(&[1])];
+        assert_eq!(crt(&residues, &moduli), None);
+    }
+
+    #[test]
+    fn crt_zero_modulus() {
+        let moduli = vec![new_from_slice(&[0]), new_from_slice(&[0b11])];
+        let residues = vec![new_from_slice(&[1]), new_from_slice(&[1])];
+        assert_eq!(crt(&residues, &moduli), None);
+    }
+
+    #[test]
+    fn crt_integer_simple() {
+        assert_eq!(crt_integer(&[2, 3, 2], &[3, 5, 7]), Some(23));
+    }
+
+    #[test]
+    fn crt_integer_negative_residues() {
+        assert_eq!(crt_integer(&[-1, -1, 6], &[4, 9, 7]), Some(251));
+    }
+
+    #[test]
+    fn crt_integer_not_coprime() {
+        assert_eq!(crt_integer(&[1, 3], &[6, 4]), None);
+    }
+
+    #[test]
+    fn crt_integer_lift_goes_negative() {
+        // x2 - x1 = 1 - 3 and the inverse of 5 mod 7 from extended_gcd is -4
+        assert_eq!(crt_integer(&[3i64, 1], &[5, 7]), Some(8));
+    }
+
+    #[test]
+    fn crt_integer_bad_moduli() {
+        assert_eq!(crt_integer(&[0i64, 1], &[0, 7]), None);
+        assert_eq!(crt_integer(&[1i64, 1], &[7, 0]), None);
+        assert_eq!(crt_integer(&[1i64, 1], &[-5, 7]), None);
+    }
+
+    #[test]
+    fn crt_integer_overflow() {
+        // the product of the moduli is past i64::MAX, but not past BigInt
+        let moduli = [4_000_000_007i64, 4_000_000_009];
+        assert_eq!(crt_integer(&[1, 2], &moduli), None);
+        let big: Vec<BigInt> = moduli.iter().map(|&m| BigInt::from(m)).collect();
+        let x = crt_integer(&[BigInt::from(1), BigInt::from(2)], &big).unwrap();
+        assert_eq!(&x % &big[0], BigInt::from(1));
+        assert_eq!(&x % &big[1], BigInt::from(2));
+    }
+
+    #[test]
+    fn crt_integer_big() {
+        let moduli: Vec<BigInt> = [1_000_000_007i64, 998_244_353, 1_000_000_009, 999_999_937]
+            .iter()
+            .map(|&m| BigInt::from(m))
+            .collect();
+        let x: BigInt = "123456789012345678901234567890".parse().unwrap();
+        let residues: Vec<BigInt> = moduli.iter().map(|m| &x % m).collect();
+        assert_eq!(crt_integer(&residues, &moduli), Some(x));
+    }
+}
@@ -0,0 +1,162 @@
+// Errors from secret sharing and from the fallible Poly operations
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    // a Shamir share where an Xor share was needed, or the other way round
+    WrongShareVariant,
+    // asked to make or combine zero shares
+    NoShares,
+    // the threshold is zero or more than the number of shares
+    InvalidThreshold {
+        threshold: usize,
+        share_count: usize,
+    },
+    // fewer shares than the threshold were given to decode
+    TooFewShares {
+        threshold: usize,
+        shares: usize,
+    },
+    // two shares (or interpolation points) have the same x-coordinate
+    DuplicateX,
+    // Xor shares of different lengths
+    ShareLengthMismatch,
+    // the secret doesn't fit in a field element
+    SecretTooLong {
+        len: usize,
+        max: usize,
+    },
+    // a different number of xs and ys to interpolate
+    LengthMismatch {
+        xs: usize,
+        ys: usize,
+    },
+    // the element has no inverse, e.g. it shares a factor with the modulus
+    NotInvertible,
+    // a Reed-Solomon code needs 0 < k < n and n distinct positions
+    InvalidCode {
+        n: usize,
+        k: usize,
+    },
+    // a message or received word of the wrong length for the code
+    WrongLength {
+        len: usize,
+        expected: usize,
+    },
+    // an erasure at a position past the end of the word
+    InvalidErasure {
+        position: usize,
+        n: usize,
+    },
+    // more errors and erasures than the code can correct
+    TooManyErrors,
+    // the system random number generator failed
+    Rng(getrandom::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Error::WrongShareVariant => write!(f, "share is the wrong kind for this sharer"),
+            Error::NoShares => write!(f, "no shares"),
+            Error::InvalidThreshold {
+                threshold,
+                share_count,
+            } => write!(
+                f,
+                "threshold {} is not between 1 and the {} shares",
+                threshold, share_count
+            ),
+            Error::TooFewShares { threshold, shares } => write!(
+                f,
+                "{} shares is fewer than the threshold of {}",
+                shares, threshold
+            ),
+            Error::DuplicateX => write!(f, "two shares have the same x-coordinate"),
+            Error::ShareLengthMismatch => write!(f, "shares have different lengths"),
+            Error::SecretTooLong { len, max } => write!(
+                f,
+                "secret of {} bytes is longer than the maximum of {}",
+                len, max
+            ),
+            Error::LengthMismatch { xs, ys } => {
+                write!(f, "{} x values but {} y values", xs, ys)
+            }
+            Error::NotInvertible => write!(f, "element is not invertible"),
+            Error::InvalidCode { n, k } => {
+                write!(f, "no Reed-Solomon code of length {} with {} symbols", n, k)
+            }
+            Error::WrongLength { len, expected } => {
+                write!(f, "{} symbols where {} were expected", len, expected)
+            }
+            Error::InvalidErasure { position, n } => {
+                write!(
+                    f,
+                    "erasure at {} is past the end of {} symbols",
+                    position, n
+                )
+            }
+            Error::TooManyErrors => write!(f, "too many errors to correct"),
+            Error::Rng(_) => write!(f, "random number generator failed"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Rng(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<getrandom::Error> for Error {
+    fn from(err: getrandom::Error) -> Self {
+        Error::Rng(err)
+    }
+}
+
+// What RngCore::try_fill_bytes returns; errors without an OS code become UNEXPECTED
+impl From<rand_core::Error> for Error {
+    fn from(err: rand_core::Error) -> Self {
+        Error::Rng(
+            err.code()
+                .map(getrandom::Error::from)
+                .unwrap_or(getrandom::Error::UNEXPECTED),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::TooFewShares {
+                    threshold: 3,
+                    shares: 2
+                }
+            ),
+            "2 shares is fewer than the threshold of 3"
+        );
+        assert_eq!(
+            format!("{}", Error::DuplicateX),
+            "two shares have the same x-coordinate"
+        );
+    }
+
+    #[test]
+    fn rng_error_is_the_source() {
+        let err = Error::from(getrandom::Error::UNSUPPORTED);
+        assert_eq!(err, Error::Rng(getrandom::Error::UNSUPPORTED));
+        assert_eq!(
+            err.source().map(|source| source.to_string()),
+            Some(getrandom::Error::UNSUPPORTED.to_string())
+        );
+        assert!(Error::NotInvertible.source().is_none());
+    }
+}
@@ -1,42 +1,335 @@
+use crate::Field;
 use crate::Poly;
-use crate::PolyTraits;
 use num::Zero;
-pub fn euclidean<T: PolyTraits<T> + num::Zero + num::One>(
+
+// Above this degree euclidean switches from the classic algorithm to half-gcd
+const HALF_GCD_CUTOFF: usize = 384;
+// At or below this degree half_gcd takes classic steps instead of recursing
+const HALF_GCD_BASE: usize = 64;
+
+// Returns (s, t, gcd) such that s * poly1 + t * poly2 = gcd
+pub fn euclidean<T: Field>(poly1: &Poly<T>, poly2: &Poly<T>) -> (Poly<T>, Poly<T>, Poly<T>) {
+    let degree = poly1.degree().max(poly2.degree()).unwrap_or(0);
+    let (s_old, t_old, remainder_old) = if degree > HALF_GCD_CUTOFF {
+        fast_euclidean(poly1, poly2)
+    } else {
+        classic_euclidean(poly1, poly2)
+    };
+    normalize_bezout(s_old, t_old, remainder_old)
+}
+
+// The quadratic extended euclidean algorithm, without the final normalization
+fn classic_euclidean<T: Field>(poly1: &Poly<T>, poly2: &Poly<T>) -> (Poly<T>, Poly<T>, Poly<T>) {
+    let mut remainder_old = poly1.clone();
+    let mut remainder_new = poly2.clone();
+    let (mut s_old, mut s_new) = (Poly::new(vec![T::one()]), Poly::new(vec![T::zero()]));
+    let (mut t_old, mut t_new) = (Poly::new(vec![T::zero()]), Poly::new(vec![T::one()]));
+    while !remainder_new.is_zero() {
+        let (quotient, remainder) = remainder_old.div(&remainder_new);
+        remainder_old = std::mem::replace(&mut remainder_new, remainder);
+
+        s_old -= &(&quotient * &s_new);
+        std::mem::swap(&mut s_old, &mut s_new);
+        t_old -= &(&quotient * &t_new);
+        std::mem::swap(&mut t_old, &mut t_new);
+    }
+    (s_old, t_old, remainder_old)
+}
+
+// Runs the extended euclidean algorithm only until the remainder has degree at
+// most `degree`, returning the unnormalized (s, t, r) with s * poly1 + t * poly2 = r
+pub fn partial_euclidean<T: Field>(
+    poly1: &Poly<T>,
+    poly2: &Poly<T>,
+    degree: usize,
+) -> (Poly<T>, Poly<T>, Poly<T>) {
+    if poly1.degree().max(poly2.degree()).unwrap_or(0) > HALF_GCD_CUTOFF {
+        fast_partial_euclidean(poly1, poly2, degree)
+    } else {
+        classic_partial_euclidean(poly1, poly2, degree)
+    }
+}
+
+// The quadratic version of partial_euclidean
+fn classic_partial_euclidean<T: Field>(
     poly1: &Poly<T>,
     poly2: &Poly<T>,
+    degree: usize,
 ) -> (Poly<T>, Poly<T>, Poly<T>) {
     let mut remainder_old = poly1.clone();
     let mut remainder_new = poly2.clone();
-    let mut quotient: Poly<T>;
     let (mut s_old, mut s_new) = (Poly::new(vec![T::one()]), Poly::new(vec![T::zero()]));
     let (mut t_old, mut t_new) = (Poly::new(vec![T::zero()]), Poly::new(vec![T::one()]));
-    let mut temp;
-    while !remainder_new.is_zero() {
-        temp = remainder_new.clone();
-        (quotient, remainder_new) = remainder_old.clone() / remainder_new.clone();
-        remainder_old = temp;
+    while remainder_old.degree().is_some_and(|d| d > degree) && !remainder_new.is_zero() {
+        let (quotient, remainder) = remainder_old.div(&remainder_new);
+        remainder_old = std::mem::replace(&mut remainder_new, remainder);
+
+        s_old -= &(&quotient * &s_new);
+        std::mem::swap(&mut s_old, &mut s_new);
+        t_old -= &(&quotient * &t_new);
+        std::mem::swap(&mut t_old, &mut t_new);
+    }
+    if remainder_old.degree().is_some_and(|d| d > degree) {
+        // the sequence ended on a zero remainder, which has every degree bound
+        return (s_new, t_new, remainder_new);
+    }
+    (s_old, t_old, remainder_old)
+}
 
-        (s_old, s_new) = (s_new.clone(), s_old.clone() - (quotient.clone() * s_new));
-        (t_old, t_new) = (t_new.clone(), t_old.clone() - (quotient.clone() * t_new));
+// Returns the monic gcd of two Polys (zero if both are zero),
+// without computing the Bézout coefficients
+pub fn gcd<T: Field>(poly1: &Poly<T>, poly2: &Poly<T>) -> Poly<T> {
+    let (mut a, mut b) = (poly1.clone(), poly2.clone());
+    while !b.is_zero() {
+        let remainder = a.modulus(&b);
+        a = std::mem::replace(&mut b, remainder);
     }
-    // normalize
-    let lc = Poly::leading_coeff(remainder_old.clone());
+    a.monic()
+}
 
-    s_old = Poly::normalize_from_value(s_old, &lc);
-    t_old = Poly::normalize_from_value(t_old, &lc);
+// Checks whether the gcd of two Polys is a (non-zero) constant
+pub(crate) fn is_coprime<T: Field>(poly1: &Poly<T>, poly2: &Poly<T>) -> bool {
+    let gcd = gcd(poly1, poly2);
+    gcd.degree() == Some(0)
+}
 
-    remainder_old = Poly::normalize(remainder_old);
+fn normalize_bezout<T: Field>(
+    s_old: Poly<T>,
+    t_old: Poly<T>,
+    remainder_old: Poly<T>,
+) -> (Poly<T>, Poly<T>, Poly<T>) {
+    if remainder_old.is_zero() {
+        return (s_old, t_old, remainder_old);
+    }
+    // divide all three by the same coefficient so the identity still holds
+    let lc = remainder_old.leading_coeff();
+
+    let s_old = Poly::normalize_from_value(s_old, &lc);
+    let t_old = Poly::normalize_from_value(t_old, &lc);
+
+    let remainder_old = Poly::normalize_from_value(remainder_old, &lc);
 
     (s_old, t_old, remainder_old)
 }
 
+// 2x2 matrix of polynomials, mapping a pair of consecutive remainders
+// (r_i, r_i+1) to a later pair (r_j, r_j+1)
+struct RemainderMatrix<T> {
+    m00: Poly<T>,
+    m01: Poly<T>,
+    m10: Poly<T>,
+    m11: Poly<T>,
+}
+
+impl<T: Field> RemainderMatrix<T> {
+    fn identity() -> Self {
+        RemainderMatrix {
+            m00: Poly::new(vec![T::one()]),
+            m01: Poly::new(vec![T::zero()]),
+            m10: Poly::new(vec![T::zero()]),
+            m11: Poly::new(vec![T::one()]),
+        }
+    }
+
+    // the matrix of a single division step: (a, b) -> (b, a - q * b)
+    fn step(quotient: Poly<T>) -> Self {
+        RemainderMatrix {
+            m00: Poly::new(vec![T::zero()]),
+            m01: Poly::new(vec![T::one()]),
+            m10: Poly::new(vec![T::one()]),
+            m11: -quotient,
+        }
+    }
+
+    fn apply(&self, a: &Poly<T>, b: &Poly<T>) -> (Poly<T>, Poly<T>) {
+        (
+            &self.m00 * a + &(&self.m01 * b),
+            &self.m10 * a + &(&self.m11 * b),
+        )
+    }
+
+    // returns self * rhs, i.e. rhs is applied first
+    fn compose(&self, rhs: &Self) -> Self {
+        RemainderMatrix {
+            m00: &self.m00 * &rhs.m00 + &(&self.m01 * &rhs.m10),
+            m01: &self.m00 * &rhs.m01 + &(&self.m01 * &rhs.m11),
+            m10: &self.m10 * &rhs.m00 + &(&self.m11 * &rhs.m10),
+            m11: &self.m10 * &rhs.m01 + &(&self.m11 * &rhs.m11),
+        }
+    }
+}
+
+// Divides by x^k, dropping the remainder
+fn shift_down<T: Field>(poly: &Poly<T>, k: usize) -> Poly<T> {
+    Poly::new(poly.coeffs().skip(k).cloned().collect())
+}
+
+// Half-gcd: given deg a > deg b, returns the matrix taking (a, b) to the consecutive
+// remainders (c, d) of the euclidean remainder sequence with deg c >= m > deg d,
+// where m = ceil(deg a / 2).
+// Only the top half of a and b decide the first half of the quotients, so both
+// recursive calls work on polynomials of half the size.
+fn half_gcd<T: Field>(a: &Poly<T>, b: &Poly<T>) -> RemainderMatrix<T> {
+    let n = a.degree().unwrap_or(0);
+    let m = n.div_ceil(2);
+    if b.degree().is_none_or(|d| d < m) {
+        return RemainderMatrix::identity();
+    }
+    if n <= HALF_GCD_BASE {
+        return classic_half_gcd(a, b, m);
+    }
+
+    let first = half_gcd(&shift_down(a, m), &shift_down(b, m));
+    let (c, d) = first.apply(a, b);
+    if d.degree().is_none_or(|deg| deg < m) {
+        return first;
+    }
+
+    let (quotient, remainder) = c.div(&d);
+    let first = RemainderMatrix::step(quotient).compose(&first);
+    let (c, d) = (d, remainder);
+    if d.degree().is_none_or(|deg| deg < m) {
+        return first;
+    }
+
+    let k = 2 * m - c.degree().unwrap();
+    let second = half_gcd(&shift_down(&c, k), &shift_down(&d, k));
+    second.compose(&first)
+}
+
+// Small case of half_gcd: takes classic division steps until deg b < m
+fn classic_half_gcd<T: Field>(a: &Poly<T>, b: &Poly<T>, m: usize) -> RemainderMatrix<T> {
+    let mut matrix = RemainderMatrix::identity();
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while b.degree().is_some_and(|d| d >= m) {
+        let (quotient, remainder) = a.div(&b);
+        a = std::mem::replace(&mut b, remainder);
+
+        matrix.m00 -= &(&quotient * &matrix.m10);
+        std::mem::swap(&mut matrix.m00, &mut matrix.m10);
+        matrix.m01 -= &(&quotient * &matrix.m11);
+        std::mem::swap(&mut matrix.m01, &mut matrix.m11);
+    }
+    matrix
+}
+
+// The extended euclidean algorithm driven by half-gcd, without the final
+// normalization. Produces exactly the same remainder sequence as classic_euclidean.
+fn fast_euclidean<T: Field>(poly1: &Poly<T>, poly2: &Poly<T>) -> (Poly<T>, Poly<T>, Poly<T>) {
+    let mut matrix = RemainderMatrix::identity();
+    let (mut a, mut b) = (poly1.clone(), poly2.clone());
+    // half_gcd needs deg a > deg b, so take ordinary steps until that holds
+    while !b.is_zero() && a.degree() <= b.degree() {
+        let (quotient, remainder) = a.div(&b);
+        matrix = RemainderMatrix::step(quotient).compose(&matrix);
+        (a, b) = (b, remainder);
+    }
+    while !b.is_zero() {
+        let reduce = half_gcd(&a, &b);
+        (a, b) = reduce.apply(&a, &b);
+        matrix = reduce.compose(&matrix);
+        if b.is_zero() {
+            break;
+        }
+        let (quotient, remainder) = a.div(&b);
+        matrix = RemainderMatrix::step(quotient).compose(&matrix);
+        (a, b) = (b, remainder);
+    }
+    (matrix.m00, matrix.m01, a)
+}
+
+// partial_euclidean driven by half-gcd. Stops at the same remainder as
+// classic_partial_euclidean, the first one of degree at most `degree`.
+fn fast_partial_euclidean<T: Field>(
+    poly1: &Poly<T>,
+    poly2: &Poly<T>,
+    degree: usize,
+) -> (Poly<T>, Poly<T>, Poly<T>) {
+    let mut matrix = RemainderMatrix::identity();
+    let (mut a, mut b) = (poly1.clone(), poly2.clone());
+    loop {
+        if a.degree().is_none_or(|d| d <= degree) {
+            return (matrix.m00, matrix.m01, a);
+        }
+        if b.degree().is_none_or(|d| d <= degree) {
+            return (matrix.m10, matrix.m11, b);
+        }
+        let n = a.degree().unwrap();
+        if b.degree().unwrap() < n {
+            // half_gcd of the top n - k coefficients stops between degree k + ceil((n - k) / 2)
+            // and the one below, which is degree + 1 once the target is within reach
+            let k = (2 * (degree + 1)).saturating_sub(n);
+            let reduce = half_gcd(&shift_down(&a, k), &shift_down(&b, k));
+            (a, b) = reduce.apply(&a, &b);
+            matrix = reduce.compose(&matrix);
+            if b.degree().is_none_or(|d| d <= degree) {
+                continue;
+            }
+        }
+        // an ordinary step, so each pass makes progress
+        let (quotient, remainder) = a.div(&b);
+        matrix = RemainderMatrix::step(quotient).compose(&matrix);
+        (a, b) = (b, remainder);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::from_integer_slice;
     use num::Rational64;
+    use proptest::prelude::*;
+    use z2z::Z2z;
 
     use super::*;
 
+    // Polys over Z2 with len + 1 coefficients and both ends one
+    fn z2z_poly(len: std::ops::Range<usize>) -> impl Strategy<Value = Poly<Z2z>> {
+        proptest::collection::vec(any::<bool>(), len).prop_map(|bits| {
+            let mut values = vec![Z2z::One];
+            values.extend(bits.into_iter().skip(1).map(Z2z::from));
+            values.push(Z2z::One);
+            Poly::new(values)
+        })
+    }
+
+    // x^d + c x^e + c0 with small integer c and c0
+    fn quotient(degrees: std::ops::Range<usize>) -> impl Strategy<Value = Poly<Rational64>> {
+        (degrees, -1..=1i64, -1..=1i64).prop_flat_map(|(d, c, c0)| {
+            (0..d).prop_map(move |e| {
+                let mut values = vec![Rational64::from_integer(0); d + 1];
+                values[d] = Rational64::from_integer(1);
+                values[e] += Rational64::from_integer(c);
+                values[0] += Rational64::from_integer(c0);
+                Poly::new(values)
+            })
+        })
+    }
+
+    // A pair of rational Polys of degree over HALF_GCD_CUTOFF whose remainder sequence
+    // is built backwards from a few monic quotients with small coefficients, so unlike
+    // random ones the coefficients stay far from overflowing i64
+    fn rational_pair() -> impl Strategy<Value = (Poly<Rational64>, Poly<Rational64>)> {
+        let small = |len| {
+            proptest::collection::vec(-2..=2i64, len).prop_map(|mut values| {
+                values.push(1);
+                from_integer_slice(&values)
+            })
+        };
+        (
+            small(1..30),
+            proptest::option::of(small(0..1)),
+            proptest::collection::vec(quotient(80..120), 5..8),
+        )
+            .prop_map(|(last, after_last, quotients)| {
+                let zero = Poly::new(vec![Rational64::from_integer(0)]);
+                let mut pair = (last, after_last.unwrap_or(zero));
+                for quotient in quotients {
+                    pair = (&quotient * &pair.0 + &pair.1, pair.0);
+                }
+                pair
+            })
+    }
+
     #[test]
     fn bookcookmath_example_polynomial_gcd() {
         assert_eq!(
@@ -116,4 +409,122 @@ mod tests {
             )
         )
     }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn half_gcd_matches_classic_z2z(a in z2z_poly(1..520), b in z2z_poly(1..520)) {
+            prop_assert_eq!(fast_euclidean(&a, &b), classic_euclidean(&a, &b));
+        }
+
+        #[test]
+        fn half_gcd_shared_factor_z2z(
+            common in z2z_poly(200..260),
+            a in z2z_poly(200..260),
+            b in z2z_poly(200..260),
+        ) {
+            // big enough that euclidean itself picks half-gcd
+            let a = a * common.clone();
+            let b = b * common.clone();
+            let (s, t, gcd) = euclidean(&a, &b);
+            prop_assert_eq!(fast_euclidean(&a, &b), classic_euclidean(&a, &b));
+            prop_assert!(gcd.modulus(&common).is_zero());
+            prop_assert_eq!(s * a + t * b, gcd);
+        }
+    }
+
+    #[test]
+    fn half_gcd_matches_classic_rational() {
+        let pairs = [
+            (vec![1, 0, -1, 0, 2, 1], vec![-1, 0, 0, 0, 1]),
+            (vec![1, 0, 1], vec![-1, 12, -20, -52, 3, 1]),
+            (vec![-1, 1, -1, -1, 1, 1], vec![1, 0, 1]),
+            (vec![2, 0, 1, 0, 0, 0, 1], vec![1, 1, 0, 1]),
+        ];
+        for (a, b) in pairs {
+            let a = from_integer_slice(&a);
+            let b = from_integer_slice(&b);
+            assert_eq!(fast_euclidean(&a, &b), classic_euclidean(&a, &b));
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn half_gcd_recurses_over_rationals((a, b) in rational_pair()) {
+            prop_assert!(a.degree().is_some_and(|d| d > HALF_GCD_CUTOFF));
+            prop_assert_eq!(fast_euclidean(&a, &b), classic_euclidean(&a, &b));
+        }
+
+        #[test]
+        fn fast_partial_euclidean_matches_classic(
+            (a, b) in rational_pair(),
+            degree in 0..500usize,
+        ) {
+            prop_assert_eq!(
+                fast_partial_euclidean(&a, &b, degree),
+                classic_partial_euclidean(&a, &b, degree)
+            );
+        }
+
+        #[test]
+        fn fast_partial_euclidean_matches_classic_z2z(
+            a in z2z_poly(400..520),
+            b in z2z_poly(1..520),
+            degree in 0..520usize,
+        ) {
+            prop_assert_eq!(
+                fast_partial_euclidean(&a, &b, degree),
+                classic_partial_euclidean(&a, &b, degree)
+            );
+        }
+    }
+
+    #[test]
+    fn partial_euclidean_stops_at_degree() {
+        let a = from_integer_slice(&vec![1, 0, -1, 0, 2, 1]);
+        let b = from_integer_slice(&vec![-1, 0, 0, 0, 1]);
+        for degree in 0..6 {
+            let (s, t, r) = partial_euclidean(&a, &b, degree);
+            assert!(r.degree().is_none_or(|d| d <= degree));
+            assert_eq!(s * a.clone() + t * b.clone(), r);
+        }
+    }
+
+    #[test]
+    fn partial_euclidean_reaches_zero() {
+        // x^2 - 1 = (x - 1)(x + 1), so the remainder after x - 1 is zero
+        let a = from_integer_slice(&vec![-1, 0, 1]);
+        let b = from_integer_slice(&vec![-1, 1]);
+        let (s, t, r) = partial_euclidean(&a, &b, 0);
+        assert!(r.is_zero());
+        assert_eq!(s * a + t * b, r);
+    }
+
+    #[test]
+    fn euclidean_normalizes_by_leading_coeff() {
+        // gcd is 2x + 4 up to a constant; the constant term and leading coefficient differ
+        let a = from_integer_slice(&vec![8, 6, 1]);
+        let b = from_integer_slice(&vec![4, 2]);
+        let (s, t, g) = euclidean(&a, &b);
+        assert_eq!(g, from_integer_slice(&vec![2, 1]));
+        assert_eq!(s * a + t * b, g);
+    }
+
+    #[test]
+    fn gcd_is_monic() {
+        // (x - 1)(x + 2) and 3(x - 1)(x - 5)
+        let a = from_integer_slice(&vec![-2, 1, 1]);
+        let b = from_integer_slice(&vec![15, -18, 3]);
+        assert_eq!(gcd(&a, &b), from_integer_slice(&vec![-1, 1]));
+    }
+
+    #[test]
+    fn gcd_with_zero_constant_term() {
+        let a = from_integer_slice(&vec![0, 0, 2]);
+        let b = from_integer_slice(&vec![0, 0, 0, 5]);
+        assert_eq!(gcd(&a, &b), from_integer_slice(&vec![0, 0, 1]));
+    }
 }
@@ -0,0 +1,73 @@
+use crate::Error;
+use crate::Field;
+use rand_core::RngCore;
+use z2z::Z2z;
+
+// An element of a finite field that can be sampled and stored as bytes
+pub trait FieldElement: Field + Eq + std::hash::Hash + Ord {
+    // The length of to_bytes
+    const BYTES: usize;
+
+    // A uniformly random element, or Error::Rng if rng fails
+    fn try_random(rng: &mut impl RngCore) -> Result<Self, Error>;
+
+    // A uniformly random element.
+    // Panics if rng fails, so take try_random where that can happen.
+    fn random(rng: &mut impl RngCore) -> Self {
+        match Self::try_random(rng) {
+            Ok(value) => value,
+            Err(err) => panic!("Random number generator failed: {}", err),
+        }
+    }
+
+    // Reads the output of to_bytes back, or None if the length is wrong
+    // or the bytes don't encode an element
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+
+    // The canonical encoding, always BYTES long, so equal elements have equal bytes
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+impl FieldElement for Z2z {
+    const BYTES: usize = 1;
+
+    // the low bit of a random byte, like Z2z::random
+    fn try_random(rng: &mut impl RngCore) -> Result<Self, Error> {
+        let mut byte = [0];
+        rng.try_fill_bytes(&mut byte)?;
+        Ok(Z2z::from(byte[0] & 1 == 1))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(Z2z::Zero),
+            [1] => Some(Z2z::One),
+            _ => None,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![**self]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn z2z_bytes() {
+        assert_eq!(Z2z::from_bytes(&Z2z::One.to_bytes()), Some(Z2z::One));
+        assert_eq!(Z2z::from_bytes(&Z2z::Zero.to_bytes()), Some(Z2z::Zero));
+        assert_eq!(Z2z::from_bytes(&[2]), None);
+        assert_eq!(Z2z::from_bytes(&[]), None);
+        assert_eq!(Z2z::from_bytes(&[1, 0]), None);
+    }
+
+    #[test]
+    fn z2z_random_takes_both_values() {
+        let draws: Vec<Z2z> = (0..128).map(|_i| Z2z::random(&mut OsRng)).collect();
+        assert!(draws.contains(&Z2z::Zero) && draws.contains(&Z2z::One));
+    }
+}
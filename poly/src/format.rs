@@ -0,0 +1,277 @@
+use crate::Poly;
+use std::fmt::Write;
+
+// How exponents and fractions are written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolyStyle {
+    // 3x^4 + 1/2
+    Plain,
+    // 3x^{4} + \frac{1}{2}
+    Latex,
+    // 3x⁴ + 1/2
+    Unicode,
+}
+
+// Builder for the ways a Poly can be written out. The default is what Display uses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolyFormatter {
+    variable: String,
+    ascending: bool,
+    style: PolyStyle,
+    subtract_negatives: bool,
+}
+
+impl Default for PolyFormatter {
+    fn default() -> Self {
+        PolyFormatter {
+            variable: "x".to_string(),
+            ascending: false,
+            style: PolyStyle::Plain,
+            subtract_negatives: false,
+        }
+    }
+}
+
+impl PolyFormatter {
+    pub fn new() -> Self {
+        PolyFormatter::default()
+    }
+
+    pub fn variable(mut self, variable: &str) -> Self {
+        self.variable = variable.to_string();
+        self
+    }
+
+    // Lowest power first rather than highest power first
+    pub fn ascending(mut self, ascending: bool) -> Self {
+        self.ascending = ascending;
+        self
+    }
+
+    pub fn style(mut self, style: PolyStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Writes "x - 1" rather than "x + -1"
+    pub fn subtract_negatives(mut self, subtract_negatives: bool) -> Self {
+        self.subtract_negatives = subtract_negatives;
+        self
+    }
+
+    pub fn format<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq>(
+        &self,
+        poly: &Poly<T>,
+    ) -> String {
+        let mut s = String::new();
+        // writing to a String can't fail
+        self.write(&mut s, poly).unwrap();
+        s
+    }
+
+    pub fn write<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq>(
+        &self,
+        f: &mut impl Write,
+        poly: &Poly<T>,
+    ) -> std::fmt::Result {
+        let mut terms: Vec<(usize, &T)> = poly
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_i, x)| !x.is_zero())
+            .collect();
+        if terms.is_empty() {
+            return write!(f, "{}", T::zero());
+        }
+        if !self.ascending {
+            terms.reverse();
+        }
+        for (i, (power, value)) in terms.into_iter().enumerate() {
+            let mut coeff = value.to_string();
+            let mut negative = false;
+            if self.subtract_negatives && coeff.starts_with('-') {
+                coeff.remove(0);
+                negative = true;
+            }
+            // one is implicit, and so is minus one once its sign has been written
+            let unit = match negative {
+                true => coeff == T::one().to_string(),
+                false => value.is_one(),
+            };
+            match (i, negative) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => (),
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            if power == 0 {
+                self.write_constant(f, &coeff)?;
+                continue;
+            }
+            if !unit {
+                self.write_coeff(f, &coeff)?;
+            }
+            write!(f, "{}", self.variable)?;
+            if power > 1 {
+                self.write_power(f, power)?;
+            }
+        }
+        Ok(())
+    }
+
+    // A constant term stands alone, so only a sum like a nested Poly needs parentheses
+    fn write_constant(&self, f: &mut impl Write, coeff: &str) -> std::fmt::Result {
+        match coeff.contains(char::is_whitespace) {
+            true => write!(f, "({})", coeff),
+            false => self.write_number(f, coeff),
+        }
+    }
+
+    fn write_coeff(&self, f: &mut impl Write, coeff: &str) -> std::fmt::Result {
+        // coefficients that are more than a number, such as nested Polys, get parentheses
+        let simple = coeff
+            .chars()
+            .enumerate()
+            .all(|(i, c)| c.is_ascii_digit() || c == '/' || c == '.' || (i == 0 && c == '-'));
+        match simple {
+            true => self.write_number(f, coeff),
+            false => write!(f, "({})", coeff),
+        }
+    }
+
+    // Writes fractions as \frac in LaTeX, and everything else as it is
+    fn write_number(&self, f: &mut impl Write, coeff: &str) -> std::fmt::Result {
+        match (self.style, coeff.split_once('/')) {
+            (PolyStyle::Latex, Some((numer, denom))) => match numer.strip_prefix('-') {
+                Some(numer) => write!(f, "-\\frac{{{}}}{{{}}}", numer, denom),
+                None => write!(f, "\\frac{{{}}}{{{}}}", numer, denom),
+            },
+            _ => write!(f, "{}", coeff),
+        }
+    }
+
+    fn write_power(&self, f: &mut impl Write, power: usize) -> std::fmt::Result {
+        match self.style {
+            PolyStyle::Plain => write!(f, "^{}", power),
+            PolyStyle::Latex => write!(f, "^{{{}}}", power),
+            PolyStyle::Unicode => {
+                for digit in power.to_string().chars() {
+                    f.write_char(SUPERSCRIPTS[digit.to_digit(10).unwrap() as usize])?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_integer_slice, new_from_slice, GF2256};
+    use num::rational::Rational64;
+    use num::{One, Zero};
+
+    fn example() -> Poly<Rational64> {
+        Poly::new(vec![
+            Rational64::new(1, 2),
+            Rational64::from_integer(-1),
+            Rational64::from_integer(0),
+            Rational64::new(-2, 3),
+            Rational64::from_integer(3),
+        ])
+    }
+
+    #[test]
+    fn default_matches_display() {
+        let t = example();
+        assert_eq!(PolyFormatter::new().format(&t), format!("{}", t));
+        assert_eq!(format!("{}", t), "3x^4 + -2/3x^3 + -1x + 1/2");
+    }
+
+    #[test]
+    fn subtract_negatives() {
+        let formatter = PolyFormatter::new().subtract_negatives(true);
+        assert_eq!(formatter.format(&example()), "3x^4 - 2/3x^3 - x + 1/2");
+        assert_eq!(
+            formatter.format(&from_integer_slice(&vec![-1, 0, -1])),
+            "-x^2 - 1"
+        );
+    }
+
+    #[test]
+    fn ascending_with_variable() {
+        let formatter = PolyFormatter::new()
+            .ascending(true)
+            .variable("t")
+            .subtract_negatives(true);
+        assert_eq!(formatter.format(&example()), "1/2 - t - 2/3t^3 + 3t^4");
+    }
+
+    #[test]
+    fn latex() {
+        let formatter = PolyFormatter::new()
+            .style(PolyStyle::Latex)
+            .subtract_negatives(true);
+        assert_eq!(
+            formatter.format(&example()),
+            "3x^{4} - \\frac{2}{3}x^{3} - x + \\frac{1}{2}"
+        );
+        assert_eq!(
+            PolyFormatter::new()
+                .style(PolyStyle::Latex)
+                .format(&example()),
+            "3x^{4} + -\\frac{2}{3}x^{3} + -1x + \\frac{1}{2}"
+        );
+    }
+
+    #[test]
+    fn unicode() {
+        let formatter = PolyFormatter::new().style(PolyStyle::Unicode);
+        assert_eq!(
+            formatter.format(&new_from_slice(&[0b11, 0b1000])),
+            "x¹¹ + x + 1"
+        );
+    }
+
+    #[test]
+    fn zero() {
+        assert_eq!(
+            PolyFormatter::new().format(&from_integer_slice(&vec![0])),
+            "0"
+        );
+    }
+
+    #[test]
+    fn nested_coefficients_get_parentheses() {
+        let t = Poly::new(vec![
+            from_integer_slice(&vec![1]),
+            from_integer_slice(&vec![1, 1]),
+        ]);
+        assert_eq!(
+            PolyFormatter::new().variable("y").format(&t),
+            "(x + 1)y + 1"
+        );
+        let t = Poly::new(vec![
+            from_integer_slice(&vec![1, 1]),
+            from_integer_slice(&vec![0, 1]),
+        ]);
+        assert_eq!(
+            PolyFormatter::new().variable("y").format(&t),
+            "(x)y + (x + 1)"
+        );
+    }
+
+    #[test]
+    fn hex_coefficients() {
+        // GF2256 writes one as 0x1, which is still left out in front of a power of x
+        let one = GF2256::one();
+        let three = GF2256::from_hex("3").unwrap();
+        let t = Poly::new(vec![one.clone(), three, GF2256::zero(), one.clone()]);
+        assert_eq!(format!("{}", t), "x^3 + (0x3)x + 0x1");
+        assert_eq!(format!("{:?}", t), format!("{}", t));
+        let t = Poly::new(vec![GF2256::zero(), GF2256::zero(), one]);
+        assert_eq!(format!("{}", t), "x^2");
+    }
+}
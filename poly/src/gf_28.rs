@@ -0,0 +1,277 @@
+use crate::Error;
+use crate::FieldElement;
+use crate::{Field, FiniteField, Ring};
+use lazy_static::lazy_static;
+use num::BigUint;
+use rand_core::RngCore;
+use serde::Deserialize;
+use serde::Serialize;
+
+// x^8 + x^4 + x^3 + x^2 + 1, the modulus of QR codes and most storage codes
+const MODULUS: u16 = 0x11d;
+
+lazy_static! {
+    // EXP[i] = x^i, written out twice so the sum of two logs needs no reduction
+    static ref EXP: [u8; 510] = {
+        let mut exp = [0; 510];
+        let mut power: u16 = 1;
+        for entry in exp.iter_mut() {
+            *entry = power as u8;
+            power <<= 1;
+            if power & 0x100 != 0 {
+                power ^= MODULUS;
+            }
+        }
+        exp
+    };
+    // LOG[EXP[i]] = i, with LOG[0] unused
+    static ref LOG: [u8; 256] = {
+        let mut log = [0; 256];
+        for i in 0..255 {
+            log[EXP[i] as usize] = i as u8;
+        }
+        log
+    };
+}
+
+// An element of GF(2^8) = Z2[x] / MODULUS, as a byte with the coefficient of x^i in bit i.
+// x generates the multiplicative group, so products go through log and exp tables.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GF28(u8);
+
+impl GF28 {
+    pub fn new(byte: u8) -> Self {
+        GF28(byte)
+    }
+
+    pub fn to_byte(&self) -> u8 {
+        self.0
+    }
+
+    // x, which has order 255
+    pub fn primitive_element() -> Self {
+        GF28(2)
+    }
+
+    // x^exponent
+    pub fn exp(exponent: usize) -> Self {
+        GF28(EXP[exponent % 255])
+    }
+
+    // The k with x^k = self, or None for zero
+    pub fn log(&self) -> Option<usize> {
+        match self.0 {
+            0 => None,
+            byte => Some(LOG[byte as usize] as usize),
+        }
+    }
+
+    // self^exponent, with 0^0 = 1
+    pub fn pow(&self, exponent: usize) -> Self {
+        match (self.log(), exponent) {
+            (_, 0) => GF28(1),
+            (None, _) => GF28(0),
+            (Some(log), _) => GF28::exp(log * (exponent % 255)),
+        }
+    }
+
+    // The multiplicative inverse, or None for zero
+    pub fn checked_inv(&self) -> Option<Self> {
+        Some(GF28::exp(255 - self.log()?))
+    }
+}
+
+impl From<u8> for GF28 {
+    fn from(byte: u8) -> Self {
+        GF28(byte)
+    }
+}
+
+impl From<GF28> for u8 {
+    fn from(value: GF28) -> Self {
+        value.0
+    }
+}
+
+impl Ring for GF28 {
+    fn characteristic() -> u64 {
+        2
+    }
+}
+
+impl Field for GF28 {
+    fn checked_inv(&self) -> Option<Self> {
+        GF28::checked_inv(self)
+    }
+}
+
+impl FiniteField for GF28 {
+    fn order() -> BigUint {
+        BigUint::from(256u16)
+    }
+
+    fn frobenius(&self) -> Self {
+        *self * *self
+    }
+}
+
+impl FieldElement for GF28 {
+    const BYTES: usize = 1;
+
+    fn try_random(rng: &mut impl RngCore) -> Result<Self, Error> {
+        let mut byte = [0];
+        rng.try_fill_bytes(&mut byte)?;
+        Ok(GF28(byte[0]))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [byte] => Some(GF28(*byte)),
+            _ => None,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        vec![self.0]
+    }
+}
+
+// Written as hex, like GF2256
+impl std::fmt::Display for GF28 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+impl std::fmt::Debug for GF28 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "GF28({:#x})", self.0)
+    }
+}
+
+impl std::ops::Add for GF28 {
+    type Output = Self;
+
+    fn add(self, rhs: GF28) -> Self::Output {
+        GF28(add_bytes(self.0, rhs.0))
+    }
+}
+
+// Every element is its own negative
+impl std::ops::Sub for GF28 {
+    type Output = Self;
+
+    fn sub(self, rhs: GF28) -> Self::Output {
+        GF28(add_bytes(self.0, rhs.0))
+    }
+}
+
+impl std::ops::Mul for GF28 {
+    type Output = Self;
+
+    fn mul(self, rhs: GF28) -> Self::Output {
+        GF28(mul_bytes(self.0, rhs.0))
+    }
+}
+
+impl std::ops::Div for GF28 {
+    type Output = Self;
+
+    fn div(self, rhs: GF28) -> Self::Output {
+        match rhs.checked_inv() {
+            Some(inverse) => GF28(mul_bytes(self.0, inverse.0)),
+            None => panic!("Division by zero error"),
+        }
+    }
+}
+
+impl num::Zero for GF28 {
+    fn zero() -> Self {
+        GF28(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl num::One for GF28 {
+    fn one() -> Self {
+        GF28(1)
+    }
+
+    fn is_one(&self) -> bool {
+        self.0 == 1
+    }
+}
+
+// The coefficients add bit by bit, mod 2
+fn add_bytes(a: u8, b: u8) -> u8 {
+    a ^ b
+}
+
+// Products add logs, and the doubled EXP covers every sum of two
+fn mul_bytes(a: u8, b: u8) -> u8 {
+    match (a, b) {
+        (0, _) | (_, 0) => 0,
+        _ => EXP[LOG[a as usize] as usize + LOG[b as usize] as usize],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{new_from_slice, Poly, GF2256};
+    use num::One;
+    use z2z::Z2z;
+
+    // The product as polynomials mod MODULUS, bit by bit
+    fn slow_mul(a: u8, b: u8) -> u8 {
+        let a = new_from_slice(&[a]);
+        let b = new_from_slice(&[b]);
+        let modulus = new_from_slice(&MODULUS.to_le_bytes());
+        let product: Poly<Z2z> = (a * b).modulus(&modulus);
+        product
+            .coeffs()
+            .enumerate()
+            .fold(0, |byte, (i, bit)| match bit {
+                Z2z::One => byte | (1 << i),
+                Z2z::Zero => byte,
+            })
+    }
+
+    #[test]
+    fn tables_match_polynomial_arithmetic() {
+        for a in (0..=255).step_by(7) {
+            for b in 0..=255 {
+                assert_eq!((GF28(a) * GF28(b)).0, slow_mul(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn inverses_and_logs() {
+        for byte in 1..=255 {
+            let a = GF28(byte);
+            assert_eq!(a * a.checked_inv().unwrap(), GF28(1));
+            assert_eq!(GF28::exp(a.log().unwrap()), a);
+        }
+        assert_eq!(GF28(0).checked_inv(), None);
+        assert_eq!(GF28(0).log(), None);
+        // x generates every non-zero element
+        let powers: std::collections::HashSet<GF28> = (0..255).map(GF28::exp).collect();
+        assert_eq!(powers.len(), 255);
+        assert_eq!(GF28::primitive_element().pow(255), GF28(1));
+        assert_eq!(GF28(0).pow(0), GF28(1));
+        assert_eq!(GF28(3).pow(3), GF28(3) * GF28(3) * GF28(3));
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        assert_eq!(GF28::from_bytes(&GF28(0x8e).to_bytes()), Some(GF28(0x8e)));
+        assert_eq!(GF28::from_bytes(&[1, 2]), None);
+        assert_eq!(u8::from(GF28::from(7)), 7);
+        assert_eq!(GF28(0x1d).to_string(), "0x1d");
+        assert_eq!(GF28(1).to_string(), GF2256::one().to_string());
+    }
+}
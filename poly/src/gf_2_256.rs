@@ -1,7 +1,13 @@
+use crate::new_from_hex;
 use crate::new_from_slice;
+use crate::Error;
+use crate::FieldElement;
 use crate::Poly;
-use crate::PolyTraits;
+use crate::SparsePoly;
+use crate::{Field, FiniteField, Ring};
 use lazy_static::lazy_static;
+use num::{BigUint, One, Zero};
+use rand_core::RngCore;
 use z2z::Z2z;
 
 use serde::Deserialize;
@@ -12,63 +18,335 @@ lazy_static! {
         1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0,
         2, 0, 1,
     ]);
+    // IRRED has only five terms, so reducing by it sparsely is much cheaper
+    static ref SPARSE_IRRED: SparsePoly<Z2z> = SparsePoly::from(IRRED.clone());
+    // x^(2^255), the square root of x
+    static ref SQRT_X: Words = {
+        let mut words = [2, 0, 0, 0];
+        for _i in 0..255 {
+            words = square_words(&words);
+        }
+        words
+    };
+    // the first x^k with trace one, used to solve quadratics
+    static ref TRACE_ONE: Words = (1..256)
+        .map(|k| {
+            let mut words = [0; 4];
+            words[k / 64] = 1 << (k % 64);
+            words
+        })
+        .find(|words| trace_words(words) == Z2z::One)
+        .unwrap();
+    // 2^256 - 1, the order of the multiplicative group
+    static ref GROUP_ORDER: BigUint = (BigUint::one() << 256u32) - BigUint::one();
 }
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+// The prime factors of 2^256 - 1, which is the product of the Fermat numbers F0 to F7
+const GROUP_ORDER_FACTORS: [u128; 11] = [
+    3,
+    5,
+    17,
+    257,
+    641,
+    65537,
+    274177,
+    6700417,
+    67280421310721,
+    59649589127497217,
+    5704689200685129054721,
+];
+
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GF2256(Poly<Z2z>);
 impl GF2256 {
     pub fn new(inner: &Poly<Z2z>) -> Self {
-        GF2256(inner.modulus(&IRRED))
+        GF2256(SPARSE_IRRED.reduce(inner))
     }
 
-    pub fn to_bytes(&self) -> Vec<u8> {
-        let values = &self.0.values;
-        let mut rvec = Vec::new();
-        let mut temp: u8 = 0;
-        for i in values.iter().enumerate() {
-            if i.0 % 8 == 0 && i.0 != 0 {
-                rvec.push(temp);
-                temp = 0;
-            }
-            match i.1 {
-                Z2z::One => temp |= 1 << (i.0 % 8),
-                Z2z::Zero => (),
-            }
+    // Reads the hex that Display and LowerHex write, or None if it isn't hex
+    // or is too big to be an element
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let inner = new_from_hex(hex)?;
+        if inner.values.len() > 256 {
+            return None;
         }
-        rvec.push(temp);
-        rvec
+        Some(GF2256(inner))
     }
+
+    // The multiplicative inverse, or None for zero
+    pub fn checked_inv(&self) -> Option<Self> {
+        // IRRED is irreducible, so only zero fails to be coprime to it
+        let inverse = self.0.try_inv_mod(&IRRED).ok()?;
+        Some(GF2256(inverse))
+    }
+
+    // FieldElement::to_bytes, which is always 32 bytes
     pub fn to_32_bytes(&self) -> Vec<u8> {
-        let mut rvec = self.to_bytes();
-        let rem = 32 - rvec.len();
-        for _i in 0..rem {
-            rvec.push(0);
+        self.to_bytes()
+    }
+}
+
+impl FieldElement for GF2256 {
+    const BYTES: usize = 32;
+
+    // every 256 bit string is an element, so 32 random bytes are exactly uniform
+    fn try_random(rng: &mut impl RngCore) -> Result<Self, Error> {
+        let mut bytes = [0; 32];
+        rng.try_fill_bytes(&mut bytes)?;
+        Ok(GF2256(new_from_slice(&bytes)))
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 32 {
+            return None;
         }
-        rvec
+        Some(GF2256(new_from_slice(bytes)))
+    }
+
+    // The coefficient of x^i is bit i % 8 of byte i / 8
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_words()
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+}
+
+// Ordered as 256 bit integers, with the coefficient of x^255 the most significant bit
+impl Ord for GF2256 {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.to_words()
+            .iter()
+            .rev()
+            .cmp(other.to_words().iter().rev())
+    }
+}
+
+impl PartialOrd for GF2256 {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
+// Written as hex rather than as a polynomial with up to 256 terms
 impl std::fmt::Display for GF2256 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", self.0)?;
+        write!(f, "{:#x}", self.0)?;
         Ok(())
     }
 }
-impl PolyTraits<GF2256> for GF2256 {}
+
+impl std::fmt::Debug for GF2256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "GF2256({:#x})", self.0)?;
+        Ok(())
+    }
+}
+
+impl std::fmt::LowerHex for GF2256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        std::fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::UpperHex for GF2256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        std::fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+// Field-theoretic operations. These all multiply many times, so they work on
+// packed words rather than on the Poly.
+impl GF2256 {
+    // self^exponent by square and multiply, with 0^0 = 1
+    pub fn pow(&self, exponent: &BigUint) -> Self {
+        GF2256::from_words(&pow_words(&self.to_words(), exponent))
+    }
+
+    // The Frobenius map a -> a^2, which only spreads the bits out before reducing
+    pub fn square(&self) -> Self {
+        GF2256::from_words(&square_words(&self.to_words()))
+    }
+
+    // The unique square root, the inverse of the Frobenius map.
+    // Splitting a into even and odd powers, a = e(x)^2 + x * o(x)^2,
+    // so sqrt(a) = e(x) + sqrt(x) * o(x).
+    pub fn sqrt(&self) -> Self {
+        let words = self.to_words();
+        let (mut even, mut odd) = ([0; 4], [0; 4]);
+        for i in 0..256 {
+            if bit(&words, i) {
+                let target = if i % 2 == 0 { &mut even } else { &mut odd };
+                target[i / 128] |= 1 << ((i / 2) % 64);
+            }
+        }
+        let odd = mul_words(&odd, &SQRT_X);
+        GF2256::from_words(&[0, 1, 2, 3].map(|i| even[i] ^ odd[i]))
+    }
+
+    // The absolute trace a + a^2 + a^4 + ... + a^(2^255), which is always 0 or 1
+    pub fn trace(&self) -> Z2z {
+        trace_words(&self.to_words())
+    }
+
+    // The absolute norm, the product of all conjugates a^(2^256 - 1),
+    // which is one for every non-zero element
+    pub fn norm(&self) -> Z2z {
+        if self.0.is_zero() {
+            Z2z::Zero
+        } else {
+            Z2z::One
+        }
+    }
+
+    // The smallest n > 0 with self^n = 1, or None for zero
+    pub fn multiplicative_order(&self) -> Option<BigUint> {
+        if self.0.is_zero() {
+            return None;
+        }
+        let words = self.to_words();
+        let mut order = GROUP_ORDER.clone();
+        // every factor of 2^256 - 1 appears once, so each can be removed at most once
+        for factor in GROUP_ORDER_FACTORS {
+            let smaller = &order / factor;
+            if pow_words(&words, &smaller) == ONE_WORDS {
+                order = smaller;
+            }
+        }
+        Some(order)
+    }
+
+    // Whether self generates the multiplicative group
+    pub fn is_primitive(&self) -> bool {
+        if self.0.is_zero() {
+            return false;
+        }
+        let words = self.to_words();
+        GROUP_ORDER_FACTORS
+            .iter()
+            .all(|factor| pow_words(&words, &(&*GROUP_ORDER / *factor)) != ONE_WORDS)
+    }
+
+    // The first primitive element, counting up through the elements as integers
+    pub fn primitive_element() -> Self {
+        (2u64..)
+            .map(|n| GF2256::from_words(&[n, 0, 0, 0]))
+            .find(|candidate| candidate.is_primitive())
+            .unwrap()
+    }
+
+    // The monic polynomial over GF(2) of least degree with self as a root.
+    // Found as the first linear dependency between 1, self, self^2, ...
+    pub fn minimal_polynomial(&self) -> Poly<Z2z> {
+        let words = self.to_words();
+        // reduced powers kept with their highest bits distinct and descending, along with
+        // which powers of self were added up to make them
+        let mut rows: Vec<(Words, [u64; 5])> = Vec::new();
+        let mut power = ONE_WORDS;
+        for k in 0..=256 {
+            let mut row = power;
+            let mut powers = [0; 5];
+            powers[k / 64] = 1 << (k % 64);
+            for (other, other_powers) in rows.iter() {
+                if bit(&row, highest_bit(other).unwrap()) {
+                    row = [0, 1, 2, 3].map(|i| row[i] ^ other[i]);
+                    powers = [0, 1, 2, 3, 4].map(|i| powers[i] ^ other_powers[i]);
+                }
+            }
+            match highest_bit(&row) {
+                None => return Poly::new((0..=k).map(|i| Z2z::from(bit(&powers, i))).collect()),
+                Some(top) => {
+                    let at = rows
+                        .iter()
+                        .position(|(other, _)| highest_bit(other).unwrap() < top)
+                        .unwrap_or(rows.len());
+                    rows.insert(at, (row, powers));
+                }
+            }
+            power = mul_words(&power, &words);
+        }
+        unreachable!("257 elements of a 256 dimensional space are dependent")
+    }
+
+    // Solves x^2 + x = self, returning one root (the other is that root plus one),
+    // or None when the trace of self is one and there are no roots.
+    // The half-trace only solves this in odd degree extensions, so with 256 this uses
+    //     x = sum over i < 255 of self^(2^i) * (d^(2^(i+1)) + ... + d^(2^255))
+    // for a fixed d of trace one.
+    pub fn solve_quadratic(&self) -> Option<Self> {
+        if self.trace() == Z2z::One {
+            return None;
+        }
+        let mut conjugates = vec![*TRACE_ONE];
+        for i in 1..256 {
+            conjugates.push(square_words(&conjugates[i - 1]));
+        }
+        let mut tail = [0; 4];
+        let mut terms = vec![[0; 4]; 255];
+        for i in (0..255).rev() {
+            tail = [0, 1, 2, 3].map(|j| tail[j] ^ conjugates[i + 1][j]);
+            terms[i] = tail;
+        }
+        let mut root = [0; 4];
+        let mut power = self.to_words();
+        for term in terms {
+            let product = mul_words(&power, &term);
+            root = [0, 1, 2, 3].map(|j| root[j] ^ product[j]);
+            power = square_words(&power);
+        }
+        Some(GF2256::from_words(&root))
+    }
+
+    fn to_words(&self) -> Words {
+        let mut words = [0; 4];
+        for (i, x) in self.0.coeffs().enumerate() {
+            if *x == Z2z::One {
+                words[i / 64] |= 1 << (i % 64);
+            }
+        }
+        words
+    }
+
+    fn from_words(words: &Words) -> Self {
+        GF2256(Poly::new(
+            (0..256).map(|i| Z2z::from(bit(words, i))).collect(),
+        ))
+    }
+}
+impl Ring for GF2256 {
+    fn characteristic() -> u64 {
+        2
+    }
+}
+
+impl Field for GF2256 {
+    fn checked_inv(&self) -> Option<Self> {
+        GF2256::checked_inv(self)
+    }
+}
+
+impl FiniteField for GF2256 {
+    fn order() -> BigUint {
+        BigUint::one() << 256usize
+    }
+
+    fn frobenius(&self) -> Self {
+        self.square()
+    }
+}
 
 impl std::ops::Add for GF2256 {
     type Output = Self;
 
+    // the sum of two reduced elements is already reduced
     fn add(self, rhs: GF2256) -> Self::Output {
-        let t = self.0 + rhs.0;
-        GF2256(t.modulus(&IRRED))
+        GF2256(self.0 + rhs.0)
     }
 }
 impl std::ops::Sub for GF2256 {
     type Output = Self;
     fn sub(self, rhs: GF2256) -> Self::Output {
-        let t = self.0 - rhs.0;
-        GF2256(t.modulus(&IRRED))
+        GF2256(self.0 - rhs.0)
     }
 }
 
@@ -76,15 +354,34 @@ impl std::ops::Mul for GF2256 {
     type Output = Self;
     fn mul(self, rhs: GF2256) -> Self::Output {
         let t = self.0 * rhs.0;
-        GF2256(t.modulus(&IRRED))
+        GF2256(SPARSE_IRRED.reduce(&t))
     }
 }
 
 impl std::ops::Div for GF2256 {
     type Output = Self;
     fn div(self, rhs: GF2256) -> Self::Output {
-        let t = rhs.0.inv_mod(&IRRED);
-        GF2256((self.0 * t).modulus(&IRRED))
+        match rhs.checked_inv() {
+            Some(inverse) => self * inverse,
+            None => panic!("Division by zero error"),
+        }
+    }
+}
+
+impl num::CheckedDiv for GF2256 {
+    fn checked_div(&self, rhs: &GF2256) -> Option<GF2256> {
+        Some(self.clone() * rhs.checked_inv()?)
+    }
+}
+
+impl num::traits::Inv for GF2256 {
+    type Output = Self;
+
+    fn inv(self) -> Self::Output {
+        match self.checked_inv() {
+            Some(inverse) => inverse,
+            None => panic!("zero has no inverse"),
+        }
     }
 }
 
@@ -93,10 +390,7 @@ impl num::Zero for GF2256 {
         GF2256(Poly::new(vec![Z2z::Zero]))
     }
     fn is_zero(&self) -> bool {
-        if self.0.values[self.0.values.len() - 1] == Z2z::Zero {
-            return true;
-        }
-        false
+        self.0.is_zero()
     }
 }
 
@@ -105,11 +399,88 @@ impl num::One for GF2256 {
         GF2256(Poly::new(vec![Z2z::One]))
     }
     fn is_one(&self) -> bool {
-        if self.0.values[self.0.values.len() - 1] == Z2z::One {
-            return true;
+        self.0.is_one()
+    }
+}
+
+// An element as 256 bits, with the coefficient of x^i in bit i % 64 of word i / 64
+type Words = [u64; 4];
+
+const ONE_WORDS: Words = [1, 0, 0, 0];
+
+fn bit(words: &[u64], i: usize) -> bool {
+    (words[i / 64] >> (i % 64)) & 1 == 1
+}
+
+fn highest_bit(words: &Words) -> Option<usize> {
+    (0..4)
+        .rev()
+        .find(|&i| words[i] != 0)
+        .map(|i| 64 * i + 63 - words[i].leading_zeros() as usize)
+}
+
+// Reduces a 512 bit product by x^256 = x^241 + x^178 + x^121 + 1, clearing the top
+// bits from the highest down so the bits they set are cleared in turn
+fn reduce_words(mut wide: [u64; 8]) -> Words {
+    for i in (256..512).rev() {
+        if bit(&wide, i) {
+            for target in [i, i - 256, i - 256 + 121, i - 256 + 178, i - 256 + 241] {
+                wide[target / 64] ^= 1 << (target % 64);
+            }
+        }
+    }
+    [wide[0], wide[1], wide[2], wide[3]]
+}
+
+// Carry-less (xor) multiplication followed by reduction
+fn mul_words(lhs: &Words, rhs: &Words) -> Words {
+    let mut wide = [0u64; 8];
+    for i in 0..256 {
+        if bit(lhs, i) {
+            // add rhs * x^i
+            let (word, shift) = (i / 64, i % 64);
+            for j in 0..4 {
+                wide[word + j] ^= rhs[j] << shift;
+                if shift != 0 {
+                    wide[word + j + 1] ^= rhs[j] >> (64 - shift);
+                }
+            }
+        }
+    }
+    reduce_words(wide)
+}
+
+// In characteristic 2 squaring is linear: x^i goes to x^(2i)
+fn square_words(words: &Words) -> Words {
+    let mut wide = [0u64; 8];
+    for i in 0..256 {
+        if bit(words, i) {
+            wide[(2 * i) / 64] |= 1 << ((2 * i) % 64);
+        }
+    }
+    reduce_words(wide)
+}
+
+fn pow_words(base: &Words, exponent: &BigUint) -> Words {
+    let mut result = ONE_WORDS;
+    for i in (0..exponent.bits()).rev() {
+        result = square_words(&result);
+        if exponent.bit(i) {
+            result = mul_words(&result, base);
         }
-        false
     }
+    result
+}
+
+fn trace_words(words: &Words) -> Z2z {
+    let mut sum = *words;
+    let mut conjugate = *words;
+    for _i in 1..256 {
+        conjugate = square_words(&conjugate);
+        sum = [0, 1, 2, 3].map(|j| sum[j] ^ conjugate[j]);
+    }
+    // the trace lies in GF(2), so only the constant bit can be set
+    Z2z::from(sum[0] & 1 == 1)
 }
 
 #[cfg(test)]
@@ -245,4 +616,209 @@ mod tests {
         let temp = new_from_slice(&t);
         cmp_with_trailing_zeros(&t, &GF2256::new(&temp).to_32_bytes())
     }
+
+    #[test]
+    fn display_as_hex() {
+        let t = GF2256::new(&new_from_slice(&[0x2f, 0x01]));
+        assert_eq!(format!("{}", t), "0x12f");
+        assert_eq!(format!("{:?}", t), "GF2256(0x12f)");
+        assert_eq!(format!("{:X}", t), "12F");
+        assert_eq!(format!("{:#06x}", t), "0x012f");
+        assert_eq!(format!("{}", GF2256::zero()), "0x0");
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let bytes: Vec<u8> = (0..32).map(|i| (i * 73 + 5) as u8).collect();
+        let t = GF2256::new(&new_from_slice(&bytes));
+        let hex = format!("{:064x}", t);
+        assert_eq!(hex.len(), 64);
+        assert_eq!(GF2256::from_hex(&hex), Some(t.clone()));
+        assert_eq!(GF2256::from_hex(&format!("{}", t)), Some(t));
+    }
+
+    #[test]
+    fn from_hex_rejects_bad_input() {
+        assert_eq!(GF2256::from_hex("0xfg"), None);
+        assert_eq!(GF2256::from_hex(""), None);
+        assert_eq!(GF2256::from_hex(&format!("1{}", "0".repeat(64))), None);
+    }
+
+    #[test]
+    fn checked_inv_and_div() {
+        use num::{traits::Inv, CheckedDiv};
+        let x = GF2256::from_hex("0x1234").unwrap();
+        assert_eq!(GF2256::zero().checked_inv(), None);
+        assert_eq!(x.checked_div(&GF2256::zero()), None);
+        assert_eq!(x.checked_div(&x), Some(GF2256::one()));
+        assert_eq!(x.clone().inv() * x, GF2256::one());
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero error")]
+    fn div_by_zero_panics() {
+        let _ = GF2256::one() / GF2256::zero();
+    }
+
+    #[test]
+    fn zero_and_one_are_constants() {
+        let x = GF2256::from_hex("0x2").unwrap();
+        assert!(!x.is_one());
+        assert!(!(x.clone() + GF2256::one()).is_one());
+        assert!(!x.is_zero());
+        assert!((x.clone() - x).is_zero());
+    }
+
+    fn examples() -> Vec<GF2256> {
+        vec![
+            GF2256::from_hex("0x2").unwrap(),
+            GF2256::from_hex("0x1b3f").unwrap(),
+            GF2256::new(&new_from_slice(
+                &(0..32u8)
+                    .map(|i| i.wrapping_mul(37) ^ 11)
+                    .collect::<Vec<u8>>(),
+            )),
+            GF2256::new(&new_from_slice(&[0xff; 32])),
+        ]
+    }
+
+    #[test]
+    fn pow_and_square_match_mul() {
+        for a in examples() {
+            assert_eq!(a.square(), a.clone() * a.clone());
+            assert_eq!(
+                a.pow(&BigUint::from(3u32)),
+                a.clone() * a.clone() * a.clone()
+            );
+            assert_eq!(a.pow(&BigUint::from(0u32)), GF2256::one());
+            // Lagrange's theorem in the multiplicative group
+            assert_eq!(a.pow(&GROUP_ORDER), GF2256::one());
+        }
+        assert_eq!(GF2256::zero().pow(&BigUint::from(0u32)), GF2256::one());
+        assert_eq!(GF2256::zero().pow(&BigUint::from(5u32)), GF2256::zero());
+    }
+
+    #[test]
+    fn sqrt_undoes_square() {
+        for a in examples() {
+            assert_eq!(a.sqrt().square(), a);
+            assert_eq!(a.square().sqrt(), a);
+        }
+    }
+
+    #[test]
+    fn trace_is_linear_and_frobenius_invariant() {
+        assert_eq!(GF2256::one().trace(), Z2z::Zero);
+        assert_eq!(GF2256::zero().trace(), Z2z::Zero);
+        let examples = examples();
+        for a in examples.iter() {
+            assert_eq!(a.square().trace(), a.trace());
+            for b in examples.iter() {
+                assert_eq!((a.clone() + b.clone()).trace(), a.trace() + b.trace());
+            }
+        }
+        assert_eq!(GF2256::from_words(&TRACE_ONE).trace(), Z2z::One);
+    }
+
+    #[test]
+    fn norm() {
+        assert_eq!(GF2256::zero().norm(), Z2z::Zero);
+        assert_eq!(examples()[1].norm(), Z2z::One);
+    }
+
+    #[test]
+    fn order_factors_multiply_to_group_order() {
+        let product = GROUP_ORDER_FACTORS
+            .iter()
+            .fold(BigUint::one(), |product, factor| product * *factor);
+        assert_eq!(product, *GROUP_ORDER);
+    }
+
+    #[test]
+    fn orders() {
+        assert_eq!(GF2256::zero().multiplicative_order(), None);
+        assert_eq!(GF2256::one().multiplicative_order(), Some(BigUint::one()));
+        let g = GF2256::primitive_element();
+        assert!(g.is_primitive());
+        assert_eq!(g.multiplicative_order(), Some(GROUP_ORDER.clone()));
+        let cube = g.pow(&BigUint::from(3u32));
+        assert!(!cube.is_primitive());
+        assert_eq!(cube.multiplicative_order(), Some(&*GROUP_ORDER / 3u32));
+        // g^((2^256 - 1) / 3) generates the multiplicative group of GF(4)
+        let third = g.pow(&(&*GROUP_ORDER / 3u32));
+        assert_eq!(third.multiplicative_order(), Some(BigUint::from(3u32)));
+        assert_eq!(third.minimal_polynomial(), new_from_slice(&[0b111]));
+    }
+
+    #[test]
+    fn minimal_polynomials() {
+        assert_eq!(GF2256::zero().minimal_polynomial(), new_from_slice(&[0b10]));
+        assert_eq!(GF2256::one().minimal_polynomial(), new_from_slice(&[0b11]));
+        // x generates the field, so its minimal polynomial is the modulus
+        assert_eq!(examples()[0].minimal_polynomial(), *IRRED);
+        for a in examples() {
+            let minimal = a.minimal_polynomial();
+            assert!(256 % minimal.degree().unwrap() == 0);
+            let over_field = minimal.map_coeffs(|x| GF2256::new(&Poly::new(vec![*x])));
+            assert!(over_field.evaluate(&a).is_zero());
+        }
+    }
+
+    #[test]
+    fn solve_quadratic() {
+        for c in examples() {
+            match c.solve_quadratic() {
+                Some(x) => {
+                    assert_eq!(c.trace(), Z2z::Zero);
+                    assert_eq!(x.square() + x, c);
+                }
+                None => assert_eq!(c.trace(), Z2z::One),
+            }
+        }
+        let c = GF2256::from_words(&TRACE_ONE);
+        assert_eq!(c.solve_quadratic(), None);
+        let c = c.clone() + examples()[1].square() + examples()[1].clone();
+        let x = c.solve_quadratic();
+        assert!(x.is_some_and(|x| x.square() + x == c) || c.trace() == Z2z::One);
+    }
+
+    #[test]
+    fn field_element_bytes() {
+        for a in examples() {
+            let bytes = FieldElement::to_bytes(&a);
+            assert_eq!(bytes.len(), GF2256::BYTES);
+            assert_eq!(GF2256::from_bytes(&bytes), Some(a));
+        }
+        assert_eq!(
+            FieldElement::to_bytes(&GF2256::from_hex("0x1234").unwrap())[..3],
+            [0x34, 0x12, 0]
+        );
+        assert_eq!(GF2256::from_bytes(&[1; 31]), None);
+        assert_eq!(GF2256::from_bytes(&[1; 33]), None);
+    }
+
+    #[test]
+    fn random_elements() {
+        let a = GF2256::random(&mut rand_core::OsRng);
+        let b = GF2256::random(&mut rand_core::OsRng);
+        assert_ne!(a, b);
+        assert_eq!(GF2256::from_bytes(&FieldElement::to_bytes(&a)), Some(a));
+    }
+
+    #[test]
+    fn ordered_as_integers() {
+        let mut elements = examples();
+        elements.push(GF2256::zero());
+        elements.push(GF2256::one());
+        elements.sort();
+        let hex: Vec<String> = elements.iter().map(|x| format!("{}", x)).collect();
+        assert_eq!(hex[..4], ["0x0", "0x1", "0x2", "0x1b3f"]);
+        assert_eq!(
+            hex[5],
+            "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+        let set: std::collections::HashSet<GF2256> =
+            examples().into_iter().chain(examples()).collect();
+        assert_eq!(set.len(), examples().len());
+    }
 }
@@ -1,29 +1,36 @@
+use crate::Error;
+use crate::Field;
 use crate::Poly;
-use crate::PolyTraits;
-pub fn interpolate<T: PolyTraits<T> + num::Zero + num::One>(
-    xs: &Vec<T>,
-    ys: &Vec<T>,
-) -> Result<Poly<T>, std::fmt::Error> {
+
+// poly at each of xs, for the codes that evaluate one Poly at many points
+pub(crate) fn evaluate_many<T: Field>(poly: &Poly<T>, xs: &[T]) -> Vec<T> {
+    xs.iter().map(|x| poly.evaluate(x)).collect()
+}
+
+pub fn interpolate<T: Field>(xs: &Vec<T>, ys: &Vec<T>) -> Result<Poly<T>, Error> {
     if xs.len() != ys.len() {
-        return Err(std::fmt::Error);
+        return Err(Error::LengthMismatch {
+            xs: xs.len(),
+            ys: ys.len(),
+        });
     }
-    let mut bases: Vec<Poly<T>> = Vec::new();
-    let mut instance: Poly<T> = Poly::new(vec![T::one()]);
+    let mut lagrange = Poly::new(vec![T::zero()]);
     for j in 0..xs.len() {
+        // the basis poly for xs[j] is the product of (x - xs[i]) / (xs[j] - xs[i]),
+        // so build the numerator in place and divide by the denominator once
+        let mut roots = Vec::with_capacity(xs.len() - 1);
+        let mut denominator = T::one();
         for i in 0..xs.len() {
             if i == j {
                 continue;
             }
-            instance = instance
-                * (Poly::new(vec![T::zero() - xs[i].clone(), T::one()])
-                    .coeff_div(xs[j].clone() - xs[i].clone()));
+            roots.push(xs[i].clone());
+            denominator = denominator * (xs[j].clone() - xs[i].clone());
         }
-        bases.push(instance.clone());
-        instance = Poly::new(vec![T::one()]);
-    }
-    let mut lagrange = Poly::new(vec![T::zero()]);
-    for i in 0..bases.len() {
-        lagrange = lagrange + bases[i].clone().coeff_mul(ys[i].clone());
+        // repeated xs leave a zero denominator
+        let inverse = denominator.checked_inv().ok_or(Error::DuplicateX)?;
+        let scale = ys[j].clone() * inverse;
+        lagrange += &Poly::from_roots(&roots).coeff_mul(scale);
     }
 
     Ok(lagrange)
@@ -61,4 +68,15 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn repeated_xs_are_an_error() {
+        let xs = vec![Rational64::from_integer(1), Rational64::from_integer(1)];
+        let ys = vec![Rational64::from_integer(2), Rational64::from_integer(3)];
+        assert_eq!(interpolate(&xs, &ys), Err(Error::DuplicateX));
+        assert_eq!(
+            interpolate(&xs, &ys[..1].to_vec()),
+            Err(Error::LengthMismatch { xs: 2, ys: 1 })
+        );
+    }
 }
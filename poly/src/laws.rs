@@ -0,0 +1,200 @@
+// Algebraic laws every coefficient type should satisfy, checked on random values.
+// A new field type gets the whole suite from one field_laws! line.
+use crate::{new_from_slice, Field, FieldElement, Poly, RationalFunction, Ring, GF2256, GF28};
+use num::rational::Rational64;
+use num::{One, Zero};
+use proptest::prelude::*;
+use std::fmt::{Debug, Display};
+use z2z::Z2z;
+
+fn check_ring<T: Ring + Debug>(a: T, b: T, c: T) -> Result<(), TestCaseError> {
+    let (zero, one) = (T::zero(), T::one());
+    prop_assert_eq!(a.clone() + b.clone(), b.clone() + a.clone());
+    prop_assert_eq!(
+        (a.clone() + b.clone()) + c.clone(),
+        a.clone() + (b.clone() + c.clone())
+    );
+    prop_assert_eq!(a.clone() * b.clone(), b.clone() * a.clone());
+    prop_assert_eq!(
+        (a.clone() * b.clone()) * c.clone(),
+        a.clone() * (b.clone() * c.clone())
+    );
+    prop_assert_eq!(
+        a.clone() * (b.clone() + c.clone()),
+        a.clone() * b.clone() + a.clone() * c.clone()
+    );
+    prop_assert_eq!(a.clone() + zero.clone(), a.clone());
+    prop_assert_eq!(a.clone() * one.clone(), a.clone());
+    prop_assert_eq!(a.clone() * zero.clone(), zero.clone());
+    prop_assert_eq!(a.clone() - a.clone(), zero.clone());
+    prop_assert_eq!((a.clone() - b.clone()) + b.clone(), a.clone());
+    // adding characteristic copies of anything gives zero
+    if T::characteristic() > 0 {
+        let sum = (0..T::characteristic()).fold(zero.clone(), |sum, _i| sum + a.clone());
+        prop_assert_eq!(sum, zero.clone());
+    }
+    check_zero_one(a)
+}
+
+fn check_field<T: Field + Debug>(a: T, b: T) -> Result<(), TestCaseError> {
+    if !a.is_zero() {
+        prop_assert_eq!(a.clone() / a.clone(), T::one());
+        prop_assert_eq!((b.clone() / a.clone()) * a.clone(), b.clone());
+        prop_assert_eq!(T::one() / (T::one() / a.clone()), a.clone());
+        prop_assert_eq!(a.inv() * a, T::one());
+    } else {
+        prop_assert!(a.checked_inv().is_none());
+    }
+    Ok(())
+}
+
+// is_zero and is_one agree with equality
+fn check_zero_one<T: Ring + Debug>(a: T) -> Result<(), TestCaseError> {
+    prop_assert_eq!(a.is_zero(), a == T::zero());
+    prop_assert_eq!(a.is_one(), a == T::one());
+    prop_assert!(T::zero().is_zero() && !T::zero().is_one());
+    prop_assert!(T::one().is_one() && !T::one().is_zero());
+    Ok(())
+}
+
+// Borrowed and in-place Poly operators agree with the owned ones
+fn check_poly_ops<T: Ring + Display>(a: Poly<T>, b: Poly<T>) -> Result<(), TestCaseError> {
+    prop_assert_eq!(&a + &b, a.clone() + b.clone());
+    prop_assert_eq!(&a - &b, a.clone() - b.clone());
+    prop_assert_eq!(&b - &a, b.clone() - a.clone());
+    prop_assert_eq!(&a * &b, a.clone() * b.clone());
+    prop_assert_eq!(-&a, Poly::zero() - a.clone());
+    let mut c = a.clone();
+    c += &b;
+    c -= &a;
+    prop_assert_eq!(&c, &b);
+    c *= &a;
+    prop_assert_eq!(c, &a * &b);
+    Ok(())
+}
+
+// Division with remainder for Polys over a field
+fn check_poly_division<T: Field + Display>(a: Poly<T>, b: Poly<T>) -> Result<(), TestCaseError> {
+    if !b.is_zero() {
+        let (quotient, remainder) = a.div(&b);
+        prop_assert!(remainder.degree() < b.degree());
+        prop_assert_eq!(&a % &b, remainder.clone());
+        prop_assert_eq!(quotient * b, a - remainder);
+    }
+    Ok(())
+}
+
+// Bytes round trip, and the byte encoding and ordering agree with equality
+fn check_field_element<T: FieldElement + Debug>(a: T, b: T) -> Result<(), TestCaseError> {
+    let bytes = a.to_bytes();
+    prop_assert_eq!(bytes.len(), T::BYTES);
+    prop_assert_eq!(T::from_bytes(&bytes), Some(a.clone()));
+    prop_assert_eq!(bytes == b.to_bytes(), a == b);
+    prop_assert_eq!(a.cmp(&b) == std::cmp::Ordering::Equal, a == b);
+    prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+    Ok(())
+}
+
+fn z2z() -> impl Strategy<Value = Z2z> {
+    any::<bool>().prop_map(Z2z::from)
+}
+
+// small enough that products of a few of them can't overflow
+fn rational() -> impl Strategy<Value = Rational64> {
+    (-9i64..10, 1i64..10).prop_map(|(n, d)| Rational64::new(n, d))
+}
+
+fn gf2256() -> impl Strategy<Value = GF2256> {
+    prop_oneof![
+        Just(GF2256::zero()),
+        Just(GF2256::one()),
+        any::<[u8; 32]>().prop_map(|bytes| GF2256::new(&new_from_slice(&bytes))),
+    ]
+}
+
+fn gf28() -> impl Strategy<Value = GF28> {
+    any::<u8>().prop_map(GF28::new)
+}
+
+fn poly<T: Ring + Display>(coeff: impl Strategy<Value = T>) -> impl Strategy<Value = Poly<T>> {
+    proptest::collection::vec(coeff, 0..8).prop_map(Poly::new)
+}
+
+fn rational_function_z2z() -> impl Strategy<Value = RationalFunction<Z2z>> {
+    (
+        poly(z2z()),
+        poly(z2z()).prop_filter("zero denominator", |d| !d.is_zero()),
+    )
+        .prop_map(|(n, d)| RationalFunction::new(n, d))
+}
+
+macro_rules! ring_laws {
+    ($name:ident, $strategy:expr) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn ring(a in $strategy, b in $strategy, c in $strategy) {
+                    check_ring(a, b, c)?;
+                }
+            }
+        }
+    };
+}
+
+macro_rules! field_laws {
+    ($name:ident, $strategy:expr) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn ring(a in $strategy, b in $strategy, c in $strategy) {
+                    check_ring(a, b, c)?;
+                }
+
+                #[test]
+                fn field(a in $strategy, b in $strategy) {
+                    check_field(a, b)?;
+                }
+
+                #[test]
+                fn poly_ring(a in poly($strategy), b in poly($strategy), c in poly($strategy)) {
+                    check_poly_ops(a.clone(), b.clone())?;
+                    check_ring(a, b, c)?;
+                }
+
+                #[test]
+                fn poly_division(a in poly($strategy), b in poly($strategy)) {
+                    check_poly_division(a, b)?;
+                }
+            }
+        }
+    };
+}
+
+macro_rules! field_element_laws {
+    ($name:ident, $strategy:expr) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #[test]
+                fn field_element(a in $strategy, b in $strategy) {
+                    check_field_element(a, b)?;
+                }
+            }
+        }
+    };
+}
+
+field_laws!(z2z_laws, z2z());
+field_laws!(rational_laws, rational());
+field_laws!(gf2256_laws, gf2256());
+field_laws!(gf28_laws, gf28());
+field_laws!(rational_function_z2z_laws, rational_function_z2z());
+ring_laws!(i64_poly_laws, poly(-1000i64..1000));
+field_element_laws!(z2z_element_laws, z2z());
+field_element_laws!(gf2256_element_laws, gf2256());
+field_element_laws!(gf28_element_laws, gf28());
@@ -0,0 +1,352 @@
+use crate::Field;
+use crate::Poly;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// How an Lfsr holds its state
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LfsrForm {
+    // the state is the next L outputs, s_n to s_(n+L-1), and each step feeds
+    // one combination of them back in
+    Fibonacci,
+    // the state is a Poly A mod the characteristic Poly P, each step multiplies
+    // it by x, and the output is the coefficient of x^(L-1)
+    Galois,
+}
+
+// A linear feedback shift register of length L over a field, with the connection
+// Poly C = 1 + c_1 x + ... + c_L x^L that berlekamp_massey returns.
+// Both forms output sequences with s_n + c_1 s_(n-1) + ... + c_L s_(n-L) = 0.
+#[derive(Clone, PartialEq)]
+pub struct Lfsr<T> {
+    form: LfsrForm,
+    connection: Poly<T>,
+    characteristic: Poly<T>,
+    state: Vec<T>,
+}
+
+impl<T: Field> Lfsr<T> {
+    // Creates an Lfsr of length state.len().
+    // Panics if C(0) isn't one or deg C is more than the length.
+    pub fn new(form: LfsrForm, connection: Poly<T>, state: Vec<T>) -> Self {
+        check_connection(&connection, state.len());
+        Lfsr {
+            form,
+            characteristic: characteristic(&connection, state.len()),
+            connection,
+            state,
+        }
+    }
+
+    pub fn fibonacci(connection: Poly<T>, state: Vec<T>) -> Self {
+        Lfsr::new(LfsrForm::Fibonacci, connection, state)
+    }
+
+    pub fn galois(connection: Poly<T>, state: Vec<T>) -> Self {
+        Lfsr::new(LfsrForm::Galois, connection, state)
+    }
+
+    pub fn form(&self) -> LfsrForm {
+        self.form
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state.is_empty()
+    }
+
+    pub fn connection(&self) -> &Poly<T> {
+        &self.connection
+    }
+
+    // P = x^L C(1/x), so the outputs satisfy P(shift) = 0
+    pub fn characteristic(&self) -> &Poly<T> {
+        &self.characteristic
+    }
+
+    pub fn state(&self) -> &[T] {
+        &self.state
+    }
+
+    // The output of the next step, without stepping
+    pub fn peek(&self) -> T {
+        let cell = match self.form {
+            LfsrForm::Fibonacci => self.state.first(),
+            LfsrForm::Galois => self.state.last(),
+        };
+        cell.cloned().unwrap_or_else(T::zero)
+    }
+
+    // Returns the next output and advances the register one step
+    pub fn step(&mut self) -> T {
+        let output = self.peek();
+        let length = self.len();
+        if length == 0 {
+            return output;
+        }
+        match self.form {
+            LfsrForm::Fibonacci => {
+                // s_(n+L) = -(c_1 s_(n+L-1) + ... + c_L s_n)
+                let feedback = (1..=length).fold(T::zero(), |sum, i| {
+                    sum - self.connection.coeff(i) * self.state[length - i].clone()
+                });
+                self.state.remove(0);
+                self.state.push(feedback);
+            }
+            LfsrForm::Galois => {
+                // x A mod P: shift up and subtract the top cell times P
+                for i in (0..length).rev() {
+                    let below = match i {
+                        0 => T::zero(),
+                        _ => self.state[i - 1].clone(),
+                    };
+                    self.state[i] = below - output.clone() * self.characteristic.coeff(i);
+                }
+            }
+        }
+        output
+    }
+
+    // Advances the register by steps at once, with O(L^2 log steps) work
+    pub fn jump(&mut self, steps: usize) {
+        let shift = Poly::monomial(T::one(), 1).pow_mod(steps, &self.characteristic);
+        self.shift_by(&shift);
+    }
+
+    // Advances the register by m steps, given shift = x^m mod P, with O(L^2) work
+    fn shift_by(&mut self, shift: &Poly<T>) {
+        let length = self.len();
+        let x = Poly::monomial(T::one(), 1);
+        let mut shift = shift.clone();
+        match self.form {
+            LfsrForm::Fibonacci => {
+                // if x^m = r_0 + ... + r_(L-1) x^(L-1) mod P then
+                // s_(n+m) = r_0 s_n + ... + r_(L-1) s_(n+L-1)
+                let mut state = Vec::with_capacity(length);
+                for _ in 0..length {
+                    state.push(recurrence_sum(&shift, &self.state));
+                    shift = (&x * &shift).modulus(&self.characteristic);
+                }
+                self.state = state;
+            }
+            LfsrForm::Galois => {
+                let cells = (shift * Poly::new(self.state.clone())).modulus(&self.characteristic);
+                self.state = (0..length).map(|i| cells.coeff(i)).collect();
+            }
+        }
+    }
+}
+
+impl<T: Field + Eq + Hash> Lfsr<T> {
+    // The least k > 0 with the state back where it is after k steps, searching up to
+    // limit by baby steps and giant steps with O(sqrt(limit)) states kept.
+    // None if the state doesn't come back that soon, or never does.
+    pub fn period(&self, limit: usize) -> Option<usize> {
+        let giant = limit.isqrt().max(1);
+        // the states after 0 to giant - 1 steps
+        let mut baby = HashMap::new();
+        let mut lfsr = self.clone();
+        for j in 0..giant {
+            if j > 0 && lfsr.state == self.state {
+                return Some(j);
+            }
+            baby.entry(lfsr.state.clone()).or_insert(j);
+            lfsr.step();
+        }
+        // after i * giant steps the state is back where it was after j steps, so
+        // (i * giant - j) steps bring it back to itself if it's on a cycle at all
+        let mut lfsr = self.clone();
+        // x^giant mod P, worked out once for every giant step
+        let shift = Poly::monomial(T::one(), 1).pow_mod(giant, &self.characteristic);
+        let mut i = 1;
+        while (i - 1) * giant < limit {
+            lfsr.shift_by(&shift);
+            if let Some(j) = baby.get(&lfsr.state) {
+                let period = i * giant - j;
+                let mut check = self.clone();
+                check.jump(period);
+                return match period <= limit && check.state == self.state {
+                    true => Some(period),
+                    false => None,
+                };
+            }
+            i += 1;
+        }
+        None
+    }
+}
+
+// Outputs forever
+impl<T: Field> Iterator for Lfsr<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        Some(self.step())
+    }
+}
+
+// The term s_n of the sequence with s_k + c_1 s_(k-1) + ... + c_L s_(k-L) = 0,
+// starting from initial = [s_0, ..., s_(L-1)], by working out x^n mod the
+// characteristic Poly (Kitamasa/Fiduccia), with O(L^2 log n) work.
+// Panics if C(0) isn't one or deg C is more than L, like Lfsr::new.
+pub fn recurrence_nth_term<T: Field>(connection: &Poly<T>, initial: &[T], n: usize) -> T {
+    check_connection(connection, initial.len());
+    let characteristic = characteristic(connection, initial.len());
+    let shift = Poly::monomial(T::one(), 1).pow_mod(n, &characteristic);
+    recurrence_sum(&shift, initial)
+}
+
+fn check_connection<T: Field>(connection: &Poly<T>, length: usize) {
+    if !connection.coeff(0).is_one() {
+        panic!("The connection Poly must have constant term one");
+    }
+    if connection.degree().is_some_and(|d| d > length) {
+        panic!("The connection Poly has more taps than the register has cells");
+    }
+}
+
+// x^length C(1/x), which is the reverse of C padded to length + 1 coefficients
+fn characteristic<T: Field>(connection: &Poly<T>, length: usize) -> Poly<T> {
+    Poly::new((0..=length).map(|i| connection.coeff(length - i)).collect())
+}
+
+// r_0 s_0 + ... + r_(L-1) s_(L-1)
+fn recurrence_sum<T: Field>(shift: &Poly<T>, terms: &[T]) -> T {
+    terms.iter().enumerate().fold(T::zero(), |sum, (i, term)| {
+        sum + shift.coeff(i) * term.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{berlekamp_massey, from_integer_slice, new_from_slice};
+    use num::rational::Rational64;
+    use z2z::Z2z;
+
+    fn bits(values: &[u8]) -> Vec<Z2z> {
+        values.iter().map(|x| Z2z::from(*x == 1)).collect()
+    }
+
+    // s_n = s_(n-3) + s_(n-4), from the primitive x^4 + x^3 + 1
+    fn primitive() -> Poly<Z2z> {
+        new_from_slice(&[0b11001])
+    }
+
+    #[test]
+    fn fibonacci_outputs_its_state_first() {
+        let lfsr = Lfsr::fibonacci(primitive(), bits(&[1, 0, 0, 0]));
+        let outputs: Vec<Z2z> = lfsr.take(15).collect();
+        assert_eq!(
+            outputs,
+            bits(&[1, 0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 0, 1, 1, 1])
+        );
+        assert_eq!(
+            Lfsr::fibonacci(primitive(), bits(&[1, 0, 0, 0])).characteristic(),
+            &new_from_slice(&[0b10011])
+        );
+    }
+
+    #[test]
+    fn both_forms_satisfy_the_recurrence() {
+        for form in [LfsrForm::Fibonacci, LfsrForm::Galois] {
+            let lfsr = Lfsr::new(form, primitive(), bits(&[0, 1, 1, 0]));
+            let outputs: Vec<Z2z> = lfsr.take(40).collect();
+            assert_eq!(berlekamp_massey(&outputs), (primitive(), 4));
+            assert_eq!(outputs[..15], outputs[15..30]);
+        }
+    }
+
+    #[test]
+    fn jump_matches_stepping() {
+        let connection = from_integer_slice(&vec![1, -2, 0, 3]);
+        let state = [1, 0, -1].map(Rational64::from_integer).to_vec();
+        for form in [LfsrForm::Fibonacci, LfsrForm::Galois] {
+            let mut stepped = Lfsr::new(form, connection.clone(), state.clone());
+            let mut jumped = stepped.clone();
+            for steps in [0, 1, 5, 12] {
+                for _ in 0..steps {
+                    stepped.step();
+                }
+                jumped.jump(steps);
+                assert_eq!(jumped.state(), stepped.state());
+            }
+        }
+    }
+
+    #[test]
+    fn periods() {
+        let lfsr = Lfsr::galois(primitive(), bits(&[1, 1, 0, 1]));
+        assert_eq!(lfsr.period(1000), Some(15));
+        assert_eq!(lfsr.period(14), None);
+        // x^4 + x^2 + 1 = (x^2 + x + 1)^2 isn't primitive, so the cycles are shorter
+        let lfsr = Lfsr::fibonacci(new_from_slice(&[0b10101]), bits(&[1, 0, 0, 0]));
+        assert_eq!(lfsr.period(1000), Some(6));
+        assert_eq!(
+            Lfsr::fibonacci(primitive(), bits(&[0; 4])).period(10),
+            Some(1)
+        );
+        // s_n = s_(n-1) with L = 2, so [1, 0] goes to [0, 0] and never comes back
+        let lfsr = Lfsr::fibonacci(new_from_slice(&[0b11]), bits(&[1, 0]));
+        assert_eq!(lfsr.period(100), None);
+        // s_n = -s_(n-1) alternates
+        let lfsr = Lfsr::fibonacci(
+            from_integer_slice(&vec![1, 1]),
+            vec![Rational64::from_integer(3)],
+        );
+        assert_eq!(lfsr.period(100), Some(2));
+    }
+
+    #[test]
+    fn empty_register_outputs_zeros() {
+        let mut lfsr = Lfsr::galois(new_from_slice(&[1]), vec![]);
+        assert!(lfsr.is_empty());
+        assert_eq!(lfsr.step(), Z2z::Zero);
+        lfsr.jump(7);
+        assert_eq!(lfsr.period(10), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "constant term one")]
+    fn connection_needs_constant_term_one() {
+        Lfsr::fibonacci(new_from_slice(&[0b10]), bits(&[1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "constant term one")]
+    fn nth_term_needs_constant_term_one() {
+        recurrence_nth_term(&new_from_slice(&[0b110]), &bits(&[1, 0]), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "more taps")]
+    fn nth_term_needs_enough_initial_terms() {
+        recurrence_nth_term(&primitive(), &bits(&[1, 0, 0]), 5);
+    }
+
+    #[test]
+    fn nth_term() {
+        // F_(n+2) = F_(n+1) + F_n
+        let fibonacci = from_integer_slice(&vec![1, -1, -1]);
+        let initial = [0, 1].map(Rational64::from_integer);
+        assert_eq!(
+            recurrence_nth_term(&fibonacci, &initial, 80),
+            Rational64::from_integer(23416728348467685)
+        );
+        assert_eq!(
+            recurrence_nth_term(&fibonacci, &initial, 1),
+            Rational64::from_integer(1)
+        );
+        let lfsr = Lfsr::fibonacci(primitive(), bits(&[0, 1, 1, 0]));
+        let outputs: Vec<Z2z> = lfsr.take(50).collect();
+        for (n, output) in outputs.iter().enumerate() {
+            assert_eq!(
+                recurrence_nth_term(&primitive(), &bits(&[0, 1, 1, 0]), n),
+                *output
+            );
+        }
+    }
+}
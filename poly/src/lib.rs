@@ -1,10 +1,15 @@
+mod algebra;
+mod error;
 mod euclidean;
+pub use algebra::{EuclideanDomain, Field, FiniteField, Ring};
 mod shamir;
+pub use error::Error;
 
 pub use euclidean::euclidean;
+pub use euclidean::gcd;
+pub use euclidean::partial_euclidean;
 use serde::Deserialize;
 use serde::Serialize;
-pub use shamir::Error;
 pub use shamir::SecretSharer;
 pub use shamir::ShamirSharer;
 pub use shamir::Share;
@@ -12,12 +17,44 @@ mod lagrange;
 pub use lagrange::interpolate;
 mod gf_2_256;
 pub use gf_2_256::GF2256;
-use num::{rational::Rational64, Zero};
+mod gf_28;
+pub use gf_28::GF28;
+mod reconstruct;
+use num::{rational::Rational64, One, Zero};
+mod crt;
+pub use crt::{crt, crt_integer};
+mod field;
+pub use field::FieldElement;
+mod power_series;
+pub use power_series::PowerSeries;
+mod mpoly;
+pub use mpoly::{groebner, groebner_f4, MPoly, MonomialOrder};
+mod format;
+pub use format::{PolyFormatter, PolyStyle};
+mod parse;
+pub use parse::{ParsePolyError, ParsePolyErrorKind, MAX_PARSE_DEGREE};
+mod rational_function;
+pub use rational_function::{PartialFractions, RationalFunction};
+mod sparse;
+pub use sparse::SparsePoly;
+mod matrix;
+pub use matrix::{LuDecomposition, Matrix};
+mod berlekamp_massey;
+pub use berlekamp_massey::{berlekamp_massey, linear_complexity_profile};
+mod lfsr;
+pub use lfsr::{recurrence_nth_term, Lfsr, LfsrForm};
+mod crc;
+pub use crc::{Crc, CrcDigest, CrcParams, CrcPreset, CRC_PRESETS};
+mod reed_solomon;
+pub use reed_solomon::ReedSolomon;
+#[cfg(test)]
+mod laws;
+pub use reconstruct::{pade, rational_reconstruct};
 use z2z::Z2z;
 
 // Struct that represents a polynomial
-// by all coefficients(in order from highest power to lowest power)
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+// by all coefficients(in order from lowest power to highest power)
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Poly<T> {
     values: Vec<T>,
 }
@@ -267,22 +304,7 @@ impl Poly2_256{
         Poly2_256::naive_div(poly, rhs).1
     }
 }
-pub trait PolyTraits<T>:
-    std::ops::Add<Output = T>
-    + std::ops::Sub<Output = T>
-    + std::ops::Mul<Output = T>
-    + std::ops::Div<Output = T>
-    + std::cmp::PartialEq
-    + Clone
-{
-}
-impl PolyTraits<Rational64> for Rational64 {}
-
-impl PolyTraits<Z2z> for Z2z {}
-
-impl PolyTraits<i64> for i64 {}
-
-impl<T: PolyTraits<T> + num::Zero + num::One> Poly<T> {
+impl<T: Ring> Poly<T> {
     // Creates new Poly from a vector of type T
     pub fn new(coeffs: Vec<T>) -> Self {
         if coeffs.len() == 0 {
@@ -290,45 +312,173 @@ impl<T: PolyTraits<T> + num::Zero + num::One> Poly<T> {
                 values: vec![T::zero()],
             }
         } else {
-            Poly {
-                values: coeffs.as_slice().to_vec(),
-            }
-            .remove_trail()
+            Poly { values: coeffs }.remove_trail()
         }
     }
 
     // Removes trailing zeros, so that the polynomials don't end up like 0x^7+0x^6... ...+15
-    fn remove_trail(self) -> Self {
-        let mut values: Vec<T> = self
+    fn remove_trail(mut self) -> Self {
+        self.trim();
+        self
+    }
+
+    // remove_trail in place
+    fn trim(&mut self) {
+        while self.values.last().is_some_and(|x| *x == T::zero()) {
+            self.values.pop();
+        }
+
+        if self.values.is_empty() {
+            self.values.push(T::zero());
+        }
+    }
+
+    // Replaces self with poly1 - self in place
+    fn sub_from(&mut self, poly1: &Poly<T>) {
+        if self.values.len() < poly1.values.len() {
+            self.values.resize(poly1.values.len(), T::zero());
+        }
+        for (i, x) in self.values.iter_mut().enumerate() {
+            *x = poly1.values.get(i).cloned().unwrap_or_else(T::zero) - x.clone();
+        }
+        self.trim();
+    }
+
+    // Creates c * x^n
+    pub fn monomial(coeff: T, n: usize) -> Self {
+        let mut values = vec![T::zero(); n];
+        values.push(coeff);
+        Poly::new(values)
+    }
+
+    // Creates the monic Poly (x - r_1)(x - r_2)... with the given roots
+    pub fn from_roots(roots: &[T]) -> Self {
+        let mut values = vec![T::one()];
+        for root in roots {
+            // multiply by (x - root) in place
+            values.insert(0, T::zero());
+            for i in 0..values.len() - 1 {
+                values[i] = values[i].clone() - root.clone() * values[i + 1].clone();
+            }
+        }
+        Poly::new(values)
+    }
+
+    // Degree of the Poly, with None standing in for the degree of zero
+    pub fn degree(&self) -> Option<usize> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.values.len() - 1)
+        }
+    }
+
+    // returns the coefficient of x^i (zero past the degree)
+    pub fn coeff(&self, i: usize) -> T {
+        self.values.get(i).cloned().unwrap_or_else(T::zero)
+    }
+
+    // returns the coefficient of the highest power (zero for the zero Poly)
+    pub fn leading_coeff(&self) -> T {
+        self.values[self.values.len() - 1].clone()
+    }
+
+    // Iterates over the coefficients from x^0 up to x^degree
+    pub fn coeffs(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.values.iter()
+    }
+
+    // Iterates over the (power, coefficient) pairs of the non-zero terms, lowest power first
+    pub fn terms(&self) -> impl DoubleEndedIterator<Item = (usize, &T)> {
+        self.values.iter().enumerate().filter(|(_i, x)| !x.is_zero())
+    }
+
+    // Applies a function to every coefficient
+    pub fn map_coeffs<U: Ring>(&self, f: impl Fn(&T) -> U) -> Poly<U> {
+        Poly::new(self.values.iter().map(f).collect())
+    }
+
+    // Reverses the coefficients, i.e. x^degree * self(1/x)
+    pub fn reverse(&self) -> Self {
+        Poly::new(self.values.iter().rev().cloned().collect())
+    }
+
+    // muls by some T rather than some Poly
+    pub fn coeff_mul(self, divisor: T) -> Poly<T> {
+        let t = self
             .values
-            .clone()
             .into_iter()
-            .rev()
-            .skip_while(|x| *x == T::zero())
+            .map(|x| x * divisor.clone())
             .collect();
-        values.reverse();
+        Poly::new(t)
+    }
 
-        if values.len() == 0 {
-            values.push(T::zero());
+    // Pseudo-division, which only needs the coefficients to form a ring:
+    // returns (q, r) with lc^(deg self - deg poly2 + 1) * self = q * poly2 + r
+    // and deg r < deg poly2, where lc is the leading coefficient of poly2
+    pub fn pseudo_div(&self, poly2: &Poly<T>) -> (Self, Self) {
+        let divisor = &poly2.values;
+        let div_len = divisor.len();
+        if divisor[div_len - 1] == T::zero() {
+            panic!("Division by zero error");
         }
-
-        Poly { values }
+        if self.values.len() < div_len {
+            return (Poly::new(vec![T::zero()]), self.clone());
+        }
+        let lc = divisor[div_len - 1].clone();
+        let mut dividend = self.values.clone();
+        let mut quotient = vec![T::zero(); dividend.len() + 1 - div_len];
+        for x in ((div_len - 1)..dividend.len()).rev() {
+            // scale everything so far by lc, then cancel the x term without dividing
+            let t = dividend[x].clone();
+            for q in quotient.iter_mut() {
+                *q = q.clone() * lc.clone();
+            }
+            quotient[x + 1 - div_len] = t.clone();
+            for value in dividend[..x].iter_mut() {
+                *value = value.clone() * lc.clone();
+            }
+            dividend[x] = T::zero();
+            for y in 1..div_len {
+                dividend[x - y] =
+                    dividend[x - y].clone() - (t.clone() * divisor[div_len - y - 1].clone());
+            }
+        }
+        (Poly::new(quotient), Poly::new(dividend))
     }
-    fn normalize(self) -> Self {
-        let mut nself = self;
-        for i in 0..nself.values.len() {
-            nself.values[i] =
-                nself.values[i].clone() / nself.values[nself.values.len() - 1].clone();
+
+    // Formal derivative of the Poly
+    pub fn derivative(&self) -> Poly<T> {
+        let mut values = Vec::new();
+        let mut power = T::zero();
+        for x in self.coeffs().skip(1) {
+            power = power + T::one();
+            values.push(x.clone() * power.clone());
         }
-        return nself;
+        Poly::new(values)
+    }
+
+    // Evaluates Poly at a given T
+    pub fn evaluate(&self, value: &T) -> T {
+        // Horner's rule, one multiplication per coefficient
+        self.values
+            .iter()
+            .rev()
+            .fold(T::zero(), |sum, x| sum * value.clone() + x.clone())
+    }
+    pub fn expose_vals(&self) -> Vec<T> {
+        self.coeffs().cloned().collect()
     }
+}
 
-    // returns the leading coefficient of the function
-    pub fn leading_coeff(self) -> T {
-        if self.values.len() == 0 {
-            return T::zero();
+// The operations that divide coefficients, so need them to form a field
+impl<T: Field> Poly<T> {
+    // Divides by the leading coefficient, leaving zero as it is
+    pub fn monic(&self) -> Self {
+        if self.is_zero() {
+            return self.clone();
         }
-        self.values[0].clone()
+        self.clone().coeff_div(self.leading_coeff())
     }
 
     // normalizes the Poly by some T
@@ -345,13 +495,38 @@ impl<T: PolyTraits<T> + num::Zero + num::One> Poly<T> {
         (self.div(poly2)).1
     }
 
+    // self^exponent mod modulus, by squaring and reducing after every product
+    pub fn pow_mod(&self, exponent: usize, modulus: &Poly<T>) -> Poly<T> {
+        let base = self.modulus(modulus);
+        let mut result = Poly::one().modulus(modulus);
+        for bit in (0..usize::BITS - exponent.leading_zeros()).rev() {
+            result = (&result * &result).modulus(modulus);
+            if (exponent >> bit) & 1 == 1 {
+                result = (result * &base).modulus(modulus);
+            }
+        }
+        result
+    }
+
     // returns the inverse of self in mod poly2
     pub fn inv_mod(&self, poly2: &Poly<T>) -> Poly<T> {
         if self.is_zero() {
             panic!("zero has no inverse");
         }
-        let (s, _t, _rem) = euclidean(self, poly2);
-        s
+        match self.try_inv_mod(poly2) {
+            Ok(inverse) => inverse,
+            Err(_) => panic!("no inverse, the Polys are not coprime"),
+        }
+    }
+
+    // returns the inverse of self in mod poly2, or an error if they aren't coprime
+    pub fn try_inv_mod(&self, poly2: &Poly<T>) -> Result<Poly<T>, Error> {
+        let (s, _t, gcd) = euclidean(self, poly2);
+        // euclidean makes the gcd monic, so coprime means it is exactly one
+        if !gcd.is_one() {
+            return Err(Error::NotInvertible);
+        }
+        Ok(s)
     }
 
     // divides some T from each coefficient
@@ -365,19 +540,22 @@ impl<T: PolyTraits<T> + num::Zero + num::One> Poly<T> {
         Poly::new(t)
     }
 
-    // muls by some T rather than some Poly
-    pub fn coeff_mul(self, divisor: T) -> Poly<T> {
-        let t = self
-            .values
-            .into_iter()
-            .map(|x| x * divisor.clone())
-            .collect();
-        Poly::new(t)
-    }
-
     // division by reference rather than by move
     pub fn div(&self, poly2: &Poly<T>) -> (Self, Self) {
-        let mut dividend = self.values.clone();
+        self.clone().into_div(poly2)
+    }
+
+    // Poly::div, but None rather than a panic when poly2 is zero
+    pub fn checked_div(&self, poly2: &Poly<T>) -> Option<(Self, Self)> {
+        if poly2.is_zero() {
+            return None;
+        }
+        Some(self.div(poly2))
+    }
+
+    // Poly::div, with the remainder worked out in self's buffer
+    fn into_div(self, poly2: &Poly<T>) -> (Self, Self) {
+        let mut dividend = self.values;
         let divisor = &poly2.values;
         let div_len = divisor.len();
         if (div_len == 0) || (divisor[div_len - 1] == T::zero()) {
@@ -386,6 +564,11 @@ impl<T: PolyTraits<T> + num::Zero + num::One> Poly<T> {
         if dividend.len() < divisor.len() {
             return (Poly::new(vec![T::zero()]), Poly::new(dividend));
         }
+        if div_len >= NEWTON_DIVISION_CUTOFF
+            && dividend.len() - div_len + 1 >= NEWTON_DIVISION_CUTOFF
+        {
+            return power_series::newton_div(&Poly { values: dividend }, poly2);
+        }
         let mut temp: Vec<T> = vec![T::zero(); dividend.len() + 1 - div_len];
         let mut t;
 
@@ -400,26 +583,6 @@ impl<T: PolyTraits<T> + num::Zero + num::One> Poly<T> {
 
         (Poly::new(temp), Poly::new(dividend))
     }
-
-    // Evaluates Poly at a given T
-    pub fn evaluate(&self, value: &T) -> T {
-        let mut sum = T::zero();
-        let mut val = T::one();
-        for i in 0..self.values.len() {
-            // get the x^power
-            for _j in 0..i {
-                val = val * value.clone();
-            }
-            // multiply by coeff, sum and reset
-            val = val * self.values[i].clone();
-            sum = sum + val.clone();
-            val = T::one();
-        }
-        return sum;
-    }
-    pub fn expose_vals(&self) -> Vec<T> {
-        self.values.clone()
-    }
 }
 pub fn expose_vals_bytes_z2z(poly: &Poly<Z2z>) -> Vec<u8> {
     let mut t = Vec::new();
@@ -442,17 +605,33 @@ pub fn from_integer_slice(coeffs: &Vec<i64>) -> Poly<Rational64> {
             .collect::<Vec<Rational64>>(),
     )
 }
-impl<T: std::fmt::Display> std::fmt::Debug for Poly<T> {
+// The same as Display, so assertion failures read like the Polys they compare
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Debug
+    for Poly<T>
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        for i in (2..self.values.len()).rev() {
-            write!(f, "{}x^{} + ", self.values[i], i)?;
-        }
-        if self.values.len() >= 2 {
-            write!(f, "{}x + ", self.values[1])?;
+        PolyFormatter::new().write(f, self)
+    }
+}
+
+// Creates a Poly<Z2Z> from a hex number (highest power first, optional 0x),
+// or None if it has a non-hex character
+pub fn new_from_hex(hex: &str) -> Option<Poly<Z2z>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.is_empty() {
+        return None;
+    }
+    let mut bits: Vec<Z2z> = Vec::new();
+    for c in hex.chars().rev() {
+        let value = c.to_digit(16)?;
+        for offset in 0..4 {
+            match (value >> offset) & 1 {
+                0 => bits.push(Z2z::Zero),
+                _ => bits.push(Z2z::One),
+            }
         }
-        write!(f, "{}", self.values[0])?;
-        Ok(())
     }
+    Some(Poly::new(bits))
 }
 
 // Creates a Poly<Z2Z> from bytes
@@ -474,173 +653,341 @@ impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::
     for Poly<T>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        let mut t: Vec<(T, usize)> = Vec::new();
-        for i in 0..self.values.len() {
-            if !self.values[i].is_zero() {
-                t.push((self.values[i].clone(), i));
-            }
-        }
-        if t.len() == 0 {
-            t.push((T::zero(), 0));
-        }
-        for i in (1..t.len()).rev() {
-            //checks if any of the remaining terms(before the final term) is the x^1 term
-            if t[i].1 == 1 {
-                if t[i].0.is_one() {
-                    write!(f, "x + ")?;
-                } else {
-                    write!(f, "{}x + ", t[i].0)?;
-                }
-            } else {
-                if t[i].0.is_one() {
-                    write!(f, "x^{} + ", t[i].1)?;
-                } else {
-                    write!(f, "{}x^{} + ", t[i].0, t[i].1)?;
-                }
-            }
-        }
-        match t[0].1 {
-            0 => write!(f, "{}", t[0].0)?,
-            1 => {
-                if t[0].0.is_one() {
-                    write!(f, "x")?
-                } else {
-                    write!(f, "{}x", t[0].0)?
-                }
-            }
-            _ => {
-                if t[0].0.is_one() {
-                    write!(f, "x^{}", t[0].1)?
-                } else {
-                    write!(f, "{}x^{}", t[0].0, t[0].1)?
-                }
-            }
-        }
-        Ok(())
+        PolyFormatter::new().write(f, self)
+    }
+}
+
+// Writes the bits of a Poly<Z2z> as a hex number, highest power first
+impl std::fmt::LowerHex for Poly<Z2z> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.pad_integral(true, "0x", &hex_digits(self, false))
+    }
+}
+
+impl std::fmt::UpperHex for Poly<Z2z> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.pad_integral(true, "0x", &hex_digits(self, true))
+    }
+}
+
+fn hex_digits(poly: &Poly<Z2z>, upper: bool) -> String {
+    let mut digits = String::new();
+    for nibble in poly.values.chunks(4).rev() {
+        let value = nibble
+            .iter()
+            .rev()
+            .fold(0, |acc, bit| (acc << 1) | (**bit as u32));
+        let digit = std::char::from_digit(value, 16).unwrap();
+        digits.push(if upper { digit.to_ascii_uppercase() } else { digit });
     }
+    digits
 }
 
-impl<T: PolyTraits<T> + num::Zero + num::One> num::Zero for Poly<T> {
+impl<T: Ring> num::Zero for Poly<T> {
     fn zero() -> Self {
         Poly::new(vec![T::zero()])
     }
+    // values never has trailing zeros, so only the constant zero is left
     fn is_zero(&self) -> bool {
-        if self.values[self.values.len() - 1] == T::zero() {
-            return true;
-        }
-        false
+        self.values.len() == 1 && self.values[0].is_zero()
     }
 }
 
-impl<T: PolyTraits<T> + num::Zero + num::One> num::One for Poly<T> {
+impl<T: Ring> num::One for Poly<T> {
     fn one() -> Self {
         Poly::new(vec![T::one()])
     }
     fn is_one(&self) -> bool {
-        if self.values[self.values.len() - 1] == T::one() {
-            return true;
-        }
-        false
+        self.values.len() == 1 && self.values[0].is_one()
     }
 }
 
-// Implements Add for the Poly
-impl<T: PolyTraits<T> + num::Zero + num::One> std::ops::Add for Poly<T> {
+// Implements Add for the Poly, reusing the buffer of the longer one
+impl<T: Ring> std::ops::Add for Poly<T> {
     type Output = Poly<T>;
 
-    fn add(self, poly2: Poly<T>) -> Self {
-        let mut higher;
-        let lower;
-        if self.values.len() > poly2.values.len() {
-            higher = self;
-            lower = poly2;
-        } else {
-            higher = poly2;
-            lower = self;
+    fn add(mut self, mut poly2: Poly<T>) -> Self {
+        if self.values.len() < poly2.values.len() {
+            std::mem::swap(&mut self, &mut poly2);
         }
+        self += &poly2;
+        self
+    }
+}
+
+impl<T: Ring> std::ops::Add<&Poly<T>> for Poly<T> {
+    type Output = Poly<T>;
+
+    fn add(mut self, poly2: &Poly<T>) -> Self {
+        self += poly2;
+        self
+    }
+}
+
+impl<T: Ring> std::ops::Add for &Poly<T> {
+    type Output = Poly<T>;
+
+    fn add(self, poly2: &Poly<T>) -> Poly<T> {
+        self.clone() + poly2
+    }
+}
 
-        for x in 0..lower.values.len() {
-            higher.values[x] = higher.values[x].clone() + lower.values[x].clone();
+impl<T: Ring> std::ops::AddAssign<&Poly<T>> for Poly<T> {
+    fn add_assign(&mut self, poly2: &Poly<T>) {
+        if self.values.len() < poly2.values.len() {
+            self.values.resize(poly2.values.len(), T::zero());
         }
+        add_shifted(&mut self.values, &poly2.values, 0);
+        self.trim();
+    }
+}
 
-        higher.remove_trail()
+impl<T: Ring> std::ops::AddAssign for Poly<T> {
+    fn add_assign(&mut self, poly2: Poly<T>) {
+        *self += &poly2;
     }
 }
 
-//Implements Sub for the Poly
-impl<T: PolyTraits<T> + num::Zero + num::One> std::ops::Sub for Poly<T> {
+//Implements Sub for the Poly, reusing the buffer of the longer one
+impl<T: Ring> std::ops::Sub for Poly<T> {
     type Output = Poly<T>;
 
-    fn sub(self, poly2: Poly<T>) -> Self {
-        let h_first;
-        let mut higher;
-        let lower;
+    fn sub(mut self, mut poly2: Poly<T>) -> Self {
         if self.values.len() >= poly2.values.len() {
-            higher = self;
-            lower = poly2;
-            h_first = true;
+            self -= &poly2;
+            self
         } else {
-            higher = poly2;
-            lower = self;
-            h_first = false;
+            poly2.sub_from(&self);
+            poly2
         }
+    }
+}
 
-        for x in 0..higher.values.len() {
-            higher.values[x] = if h_first {
-                higher.values[x].clone() - lower.values.get(x).unwrap_or(&T::zero()).clone()
-            } else {
-                lower.values.get(x).unwrap_or(&T::zero()).clone() - higher.values[x].clone()
-            };
+impl<T: Ring> std::ops::Sub<&Poly<T>> for Poly<T> {
+    type Output = Poly<T>;
+
+    fn sub(mut self, poly2: &Poly<T>) -> Self {
+        self -= poly2;
+        self
+    }
+}
+
+impl<T: Ring> std::ops::Sub for &Poly<T> {
+    type Output = Poly<T>;
+
+    fn sub(self, poly2: &Poly<T>) -> Poly<T> {
+        self.clone() - poly2
+    }
+}
+
+impl<T: Ring> std::ops::SubAssign<&Poly<T>> for Poly<T> {
+    fn sub_assign(&mut self, poly2: &Poly<T>) {
+        if self.values.len() < poly2.values.len() {
+            self.values.resize(poly2.values.len(), T::zero());
+        }
+        for (target, x) in self.values.iter_mut().zip(poly2.values.iter()) {
+            *target = target.clone() - x.clone();
+        }
+        self.trim();
+    }
+}
+
+impl<T: Ring> std::ops::SubAssign for Poly<T> {
+    fn sub_assign(&mut self, poly2: Poly<T>) {
+        *self -= &poly2;
+    }
+}
+
+impl<T: Ring> std::ops::Neg for Poly<T> {
+    type Output = Poly<T>;
+
+    fn neg(mut self) -> Self {
+        // the coefficients only have Sub, so negate as 0 - x
+        for x in self.values.iter_mut() {
+            *x = T::zero() - x.clone();
         }
+        self
+    }
+}
 
-        higher.remove_trail()
+impl<T: Ring> std::ops::Neg for &Poly<T> {
+    type Output = Poly<T>;
+
+    fn neg(self) -> Poly<T> {
+        -self.clone()
     }
 }
 
-impl<T: PolyTraits<T> + num::Zero + num::One> std::ops::Mul for Poly<T> {
+impl<T: Ring> std::ops::Mul for Poly<T> {
     type Output = Poly<T>;
 
     fn mul(self, poly2: Poly<T>) -> Poly<T> {
-        let new_power = self.values.len() + poly2.values.len() - 1;
+        &self * &poly2
+    }
+}
+
+impl<T: Ring> std::ops::Mul<&Poly<T>> for Poly<T> {
+    type Output = Poly<T>;
+
+    fn mul(self, poly2: &Poly<T>) -> Poly<T> {
+        &self * poly2
+    }
+}
+
+impl<T: Ring> std::ops::Mul for &Poly<T> {
+    type Output = Poly<T>;
+
+    fn mul(self, poly2: &Poly<T>) -> Poly<T> {
+        Poly::new(mul_slices(&self.values, &poly2.values))
+    }
+}
+
+impl<T: Ring> std::ops::MulAssign<&Poly<T>> for Poly<T> {
+    fn mul_assign(&mut self, poly2: &Poly<T>) {
+        *self = &*self * poly2;
+    }
+}
+
+impl<T: Ring> std::ops::MulAssign for Poly<T> {
+    fn mul_assign(&mut self, poly2: Poly<T>) {
+        *self = &*self * &poly2;
+    }
+}
+
+// Implements Rem for the Poly as the remainder of Poly::div
+impl<T: Field> std::ops::Rem for Poly<T> {
+    type Output = Poly<T>;
+
+    fn rem(self, poly2: Poly<T>) -> Poly<T> {
+        self.into_div(&poly2).1
+    }
+}
+
+impl<T: Field> std::ops::Rem<&Poly<T>> for Poly<T> {
+    type Output = Poly<T>;
+
+    fn rem(self, poly2: &Poly<T>) -> Poly<T> {
+        self.into_div(poly2).1
+    }
+}
+
+impl<T: Field> std::ops::Rem for &Poly<T> {
+    type Output = Poly<T>;
+
+    fn rem(self, poly2: &Poly<T>) -> Poly<T> {
+        self.modulus(poly2)
+    }
+}
+
+impl<T: Ring> std::iter::Sum for Poly<T> {
+    fn sum<I: Iterator<Item = Poly<T>>>(iter: I) -> Self {
+        iter.fold(Poly::new(vec![T::zero()]), |sum, x| sum + x)
+    }
+}
+
+impl<'a, T: Ring + 'a> std::iter::Sum<&'a Poly<T>> for Poly<T> {
+    fn sum<I: Iterator<Item = &'a Poly<T>>>(iter: I) -> Self {
+        iter.fold(Poly::new(vec![T::zero()]), |sum, x| sum + x)
+    }
+}
+
+impl<T: Ring> std::iter::Product for Poly<T> {
+    fn product<I: Iterator<Item = Poly<T>>>(iter: I) -> Self {
+        iter.fold(Poly::new(vec![T::one()]), |product, x| product * x)
+    }
+}
+
+impl<'a, T: Ring + 'a> std::iter::Product<&'a Poly<T>> for Poly<T> {
+    fn product<I: Iterator<Item = &'a Poly<T>>>(iter: I) -> Self {
+        iter.fold(Poly::new(vec![T::one()]), |product, x| product * x)
+    }
+}
+
+// Below this many coefficients schoolbook multiplication is faster than karatsuba
+const KARATSUBA_CUTOFF: usize = 512;
+// Long division is used unless both the divisor and the quotient have at least this many
+// coefficients, where dividing through a power series inverse is faster
+const NEWTON_DIVISION_CUTOFF: usize = 2048;
+
+// Multiplies two coefficient slices, using karatsuba when both are long enough
+fn mul_slices<T: Ring>(lhs: &[T], rhs: &[T]) -> Vec<T> {
+    if lhs.len() < KARATSUBA_CUTOFF || rhs.len() < KARATSUBA_CUTOFF {
+        let new_power = lhs.len() + rhs.len() - 1;
         // Allocate a new vec of the required length
         let mut accum = vec![T::zero(); new_power];
         // Loops through both vecs and mults them(added to the stuff already in there)
-        for x in 0..self.values.len() {
-            for y in 0..poly2.values.len() {
-                accum[x + y] =
-                    accum[x + y].clone() + (self.values[x].clone() * poly2.values[y].clone());
+        for x in 0..lhs.len() {
+            for y in 0..rhs.len() {
+                accum[x + y] = accum[x + y].clone() + (lhs[x].clone() * rhs[y].clone());
             }
         }
-        Poly::new(accum)
+        return accum;
+    }
+    let mut accum = vec![T::zero(); lhs.len() + rhs.len() - 1];
+    let half = lhs.len().max(rhs.len()) / 2;
+    // split both sides as low + x^half * high
+    let (lhs_low, lhs_high) = lhs.split_at(half.min(lhs.len()));
+    let (rhs_low, rhs_high) = rhs.split_at(half.min(rhs.len()));
+    if lhs_high.is_empty() || rhs_high.is_empty() {
+        // one side is short, so multiply it by both halves of the other one
+        let (long_low, long_high, short) = if lhs_high.is_empty() {
+            (rhs_low, rhs_high, lhs)
+        } else {
+            (lhs_low, lhs_high, rhs)
+        };
+        add_shifted(&mut accum, &mul_slices(long_low, short), 0);
+        add_shifted(&mut accum, &mul_slices(long_high, short), half);
+        return accum;
+    }
+    let low = mul_slices(lhs_low, rhs_low);
+    let high = mul_slices(lhs_high, rhs_high);
+    // (l0 + l1)(r0 + r1) - l0r0 - l1r1 gives the middle term with one multiplication
+    let mut middle = mul_slices(
+        &add_slices(lhs_low, lhs_high),
+        &add_slices(rhs_low, rhs_high),
+    );
+    for (i, x) in low.iter().enumerate() {
+        middle[i] = middle[i].clone() - x.clone();
+    }
+    for (i, x) in high.iter().enumerate() {
+        middle[i] = middle[i].clone() - x.clone();
+    }
+    add_shifted(&mut accum, &low, 0);
+    add_shifted(&mut accum, &middle, half);
+    add_shifted(&mut accum, &high, 2 * half);
+    accum
+}
+
+// Adds two coefficient slices of possibly different lengths
+fn add_slices<T: Ring>(lhs: &[T], rhs: &[T]) -> Vec<T> {
+    let mut sum = vec![T::zero(); lhs.len().max(rhs.len())];
+    for (i, x) in lhs.iter().enumerate() {
+        sum[i] = x.clone();
+    }
+    for (i, x) in rhs.iter().enumerate() {
+        sum[i] = sum[i].clone() + x.clone();
     }
+    sum
 }
 
-impl<T: PolyTraits<T> + num::Zero + num::One> std::ops::Div for Poly<T> {
-    type Output = (Self, Self);
+// Adds values into accum starting at offset; anything past the end of accum is zero
+fn add_shifted<T: Ring>(accum: &mut [T], values: &[T], offset: usize) {
+    for (target, x) in accum.iter_mut().skip(offset).zip(values.iter()) {
+        *target = target.clone() + x.clone();
+    }
+}
 
-    fn div(self, poly2: Poly<T>) -> Self::Output {
-        let mut dividend = self.values;
-        let divisor = poly2.values;
-        let div_len = divisor.len();
-        if (div_len == 0) || (divisor[div_len - 1] == T::zero()) {
-            panic!("Division by zero error");
-        }
-        if dividend.len() < divisor.len() {
-            return (Poly::new(vec![T::zero()]), Poly::new(dividend));
-        }
-        let mut temp: Vec<T> = vec![T::zero(); dividend.len() + 1 - div_len];
-        let mut t;
+// Exact division of Polys over a field.
+// Panics if poly2 does not divide self; Poly::div gives the quotient and remainder.
+impl<T: Field> std::ops::Div for Poly<T> {
+    type Output = Poly<T>;
 
-        for x in ((div_len - 1)..dividend.len()).rev() {
-            t = dividend[x].clone() / divisor[div_len - 1].clone();
-            temp[x + 1 - div_len] = t.clone();
-            for y in 0..div_len {
-                dividend[x - y] =
-                    dividend[x - y].clone() - (t.clone() * divisor[div_len - y - 1].clone());
-            }
+    fn div(self, poly2: Poly<T>) -> Self::Output {
+        let (quotient, remainder) = Poly::div(&self, &poly2);
+        if !remainder.is_zero() {
+            panic!("Inexact division error");
         }
-
-        (Poly::new(temp), Poly::new(dividend))
+        quotient
     }
 }
 
@@ -816,6 +1163,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn karatsuba_matches_schoolbook() {
+        // long enough on both sides to take the karatsuba path, including an unbalanced split
+        let lhs: Vec<i64> = (0..1500).map(|i| (i * 7919) % 13 - 6).collect();
+        let rhs: Vec<i64> = (0..700).map(|i| (i * 104729) % 11 - 5).collect();
+        let mut expected = vec![0; lhs.len() + rhs.len() - 1];
+        for x in 0..lhs.len() {
+            for y in 0..rhs.len() {
+                expected[x + y] += lhs[x] * rhs[y];
+            }
+        }
+        assert_eq!(Poly::new(lhs) * Poly::new(rhs), Poly::new(expected));
+    }
+
     #[test]
     fn mul_by_zero() {
         let tvec = vec![1, 2, 3];
@@ -837,7 +1198,7 @@ mod tests {
     #[test]
     fn check_div_len() {
         assert_eq!(
-            (from_integer_slice(&vec![0, 1, 2, 3]) / from_integer_slice(&vec![0, 1]))
+            (from_integer_slice(&vec![0, 1, 2, 3]).div(&from_integer_slice(&vec![0, 1])))
                 .0
                 .values
                 .len(),
@@ -847,7 +1208,7 @@ mod tests {
     #[test]
     fn check_div_exact() {
         assert_eq!(
-            (from_integer_slice(&vec![-4, 0, -2, 1]) / from_integer_slice(&vec![-3, 1])),
+            (from_integer_slice(&vec![-4, 0, -2, 1]).div(&from_integer_slice(&vec![-3, 1]))),
             (
                 from_integer_slice(&vec![3, 1, 1]),
                 from_integer_slice(&vec![5])
@@ -858,8 +1219,7 @@ mod tests {
     #[test]
     fn div_by_longer_poly() {
         assert_eq!(
-            (from_integer_slice(&vec![4, 0, 0, 1])
-                / from_integer_slice(&vec![6, 0, 0, 0, 0, 0, 0, 1])),
+            (from_integer_slice(&vec![4, 0, 0, 1]).div(&from_integer_slice(&vec![6, 0, 0, 0, 0, 0, 0, 1]))),
             (
                 from_integer_slice(&vec![0]),
                 from_integer_slice(&vec![4, 0, 0, 1])
@@ -871,7 +1231,7 @@ mod tests {
     fn div_by_equal_len() {
         // x^3 + 4 = (x^3 + 6) * 1 - 2
         assert_eq!(
-            (from_integer_slice(&vec![4, 0, 0, 1]) / from_integer_slice(&vec![6, 0, 0, 1])),
+            (from_integer_slice(&vec![4, 0, 0, 1]).div(&from_integer_slice(&vec![6, 0, 0, 1]))),
             (from_integer_slice(&vec![1]), from_integer_slice(&vec![-2]))
         );
     }
@@ -880,7 +1240,7 @@ mod tests {
     fn simple_div_by_equal_len() {
         // x^3 + 4 = (x^3 + 6) * 1 - 2
         assert_eq!(
-            (from_integer_slice(&vec![8, 0, 0, 2]) / from_integer_slice(&vec![4, 0, 0, 1])),
+            (from_integer_slice(&vec![8, 0, 0, 2]).div(&from_integer_slice(&vec![4, 0, 0, 1]))),
             (from_integer_slice(&vec![2]), from_integer_slice(&vec![0]))
         );
     }
@@ -983,7 +1343,7 @@ mod tests {
     fn poly_debug_with_zeros_in_middle() {
         assert_eq!(
             format!("{:?}", from_integer_slice(&vec![1, 0, 0, 0, 4, 5, 6])),
-            String::from("6x^6 + 5x^5 + 4x^4 + 0x^3 + 0x^2 + 0x + 1")
+            String::from("6x^6 + 5x^5 + 4x^4 + 1")
         );
     }
 
@@ -991,7 +1351,7 @@ mod tests {
     fn poly_debug_with_zero_at_end() {
         assert_eq!(
             format!("{:?}", from_integer_slice(&vec![0, 0, 0, 0, 4, 5, 6])),
-            String::from("6x^6 + 5x^5 + 4x^4 + 0x^3 + 0x^2 + 0x + 0")
+            String::from("6x^6 + 5x^5 + 4x^4")
         );
     }
 
@@ -1027,6 +1387,50 @@ mod tests {
         assert!(Poly::modulus(&(inv * t), &irred).is_one())
     }
 
+    #[test]
+    fn try_inv_mod_needs_coprime() {
+        let irred = new_from_slice(&[0x13]);
+        let t = new_from_slice(&[5]);
+        assert_eq!(t.try_inv_mod(&irred).ok(), Some(t.inv_mod(&irred)));
+        // x^2 + 1 = (x + 1)^2 shares a factor with x + 1
+        let square = new_from_slice(&[0b101]);
+        assert_eq!(
+            new_from_slice(&[0b11]).try_inv_mod(&square),
+            Err(Error::NotInvertible)
+        );
+        assert!(new_from_slice(&[0]).try_inv_mod(&irred).is_err());
+    }
+
+    #[test]
+    fn pow_mod() {
+        // x^4 + x + 1 is primitive, so x has order 15 mod it
+        let irred = new_from_slice(&[0x13]);
+        let x = new_from_slice(&[0b10]);
+        assert!(x.pow_mod(15, &irred).is_one());
+        assert!(!x.pow_mod(5, &irred).is_one());
+        assert!(x.pow_mod(0, &irred).is_one());
+        let t = new_from_slice(&[0b1011]);
+        let cubed = (&t * &t * &t).modulus(&irred);
+        assert_eq!(t.pow_mod(3, &irred), cubed);
+        assert!(t.pow_mod(3, &new_from_slice(&[1])).is_zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "not coprime")]
+    fn inv_mod_panics_when_not_coprime() {
+        new_from_slice(&[0b11]).inv_mod(&new_from_slice(&[0b101]));
+    }
+
+    #[test]
+    fn checked_div() {
+        let a = from_integer_slice(&vec![1, 2, 1]);
+        assert_eq!(a.checked_div(&from_integer_slice(&vec![0])), None);
+        assert_eq!(
+            a.checked_div(&from_integer_slice(&vec![1, 1])),
+            Some(a.div(&from_integer_slice(&vec![1, 1])))
+        );
+    }
+
     #[test]
     fn inv_mod_gf_2_256() {
         let t = new_from_slice(&vec![5]);
@@ -1045,9 +1449,9 @@ mod tests {
 
     #[test]
     fn coeff_div_simple() {
-        let t = Poly::new(vec![2, 4, 6, 8]);
-        let temp = t.coeff_div(2);
-        assert_eq!(temp, Poly::new(vec![1, 2, 3, 4]));
+        let t = from_integer_slice(&vec![2, 4, 6, 8]);
+        let temp = t.coeff_div(Rational64::from_integer(2));
+        assert_eq!(temp, from_integer_slice(&vec![1, 2, 3, 4]));
     }
 
     #[test]
@@ -1057,6 +1461,18 @@ mod tests {
         assert_eq!(temp, Poly::new(vec![6, 12, 18, 24]));
     }
 
+    #[test]
+    fn derivative_simple() {
+        let t = from_integer_slice(&vec![5, 3, 0, 2]);
+        assert_eq!(t.derivative(), from_integer_slice(&vec![3, 0, 6]));
+    }
+
+    #[test]
+    fn derivative_of_constant() {
+        let t = from_integer_slice(&vec![5]);
+        assert_eq!(t.derivative(), from_integer_slice(&vec![0]));
+    }
+
     #[test]
     fn evaluate_poly_ints() {
         let t = from_integer_slice(&vec![1, 2, 3]);
@@ -1086,4 +1502,212 @@ mod tests {
             Rational64::from_integer(257)
         );
     }
+
+    #[test]
+    fn exact_div_operator() {
+        assert_eq!(
+            from_integer_slice(&vec![-1, 0, 1]) / from_integer_slice(&vec![-1, 1]),
+            from_integer_slice(&vec![1, 1])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Inexact division error")]
+    fn inexact_div_operator_panics() {
+        let _ = from_integer_slice(&vec![1, 0, 1]) / from_integer_slice(&vec![-1, 1]);
+    }
+
+    #[test]
+    fn pseudo_div_over_integers() {
+        // 2^3 (x^3 + 2x + 1) = q (2x + 1) + r without leaving the integers
+        let a = Poly::new(vec![1i64, 2, 0, 1]);
+        let b = Poly::new(vec![1i64, 2]);
+        let (q, r) = a.pseudo_div(&b);
+        assert_eq!(r.values.len(), 1);
+        assert_eq!(a * Poly::new(vec![8i64]), q * b + r);
+    }
+
+    // Poly in y with coefficients that are Polys in x
+    fn bivariate(coeffs: &[&[i64]]) -> Poly<Poly<Rational64>> {
+        Poly::new(
+            coeffs
+                .iter()
+                .map(|c| from_integer_slice(&c.to_vec()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn nested_poly_arithmetic() {
+        // (y + x)(y - x) = y^2 - x^2
+        let sum = bivariate(&[&[0, 1], &[1]]);
+        let difference = bivariate(&[&[0, -1], &[1]]);
+        let product = sum.clone() * difference.clone();
+        assert_eq!(product, bivariate(&[&[0, 0, -1], &[0], &[1]]));
+        // Q[x] is not a field, but difference is monic in y so pseudo-division is exact
+        let (quotient, remainder) = product.pseudo_div(&difference);
+        assert!(remainder.is_zero());
+        assert_eq!(quotient, sum);
+        // y = x is a root
+        assert!(product.evaluate(&from_integer_slice(&vec![0, 1])).is_zero());
+    }
+
+    #[test]
+    fn nested_poly_pseudo_div() {
+        // x y + 1 has a leading coefficient x that is not invertible in Q[x]
+        let a = bivariate(&[&[1], &[0], &[1]]);
+        let b = bivariate(&[&[1], &[0, 1]]);
+        let (q, r) = a.pseudo_div(&b);
+        assert_eq!(r.values.len(), 1);
+        let scale = Poly::new(vec![from_integer_slice(&vec![0, 0, 1])]);
+        assert_eq!(a * scale, q * b + r);
+    }
+
+    #[test]
+    fn z2z_poly_hex() {
+        let t = new_from_slice(&[0x13, 0xa0]);
+        assert_eq!(format!("{:x}", t), "a013");
+        assert_eq!(format!("{:#X}", t), "0xA013");
+        assert_eq!(new_from_hex("0xa013"), Some(t));
+        assert_eq!(format!("{:x}", new_from_slice(&[0])), "0");
+        assert_eq!(new_from_hex("0x"), None);
+    }
+
+    #[test]
+    fn leading_coeff_is_highest_power() {
+        let t = from_integer_slice(&vec![7, 0, 3]);
+        assert_eq!(t.leading_coeff(), Rational64::from_integer(3));
+        assert_eq!(
+            from_integer_slice(&vec![0]).leading_coeff(),
+            Rational64::zero()
+        );
+    }
+
+    #[test]
+    fn degree_and_coeff() {
+        let t = from_integer_slice(&vec![7, 0, 3]);
+        assert_eq!(t.degree(), Some(2));
+        assert_eq!(from_integer_slice(&vec![5]).degree(), Some(0));
+        assert_eq!(from_integer_slice(&vec![0]).degree(), None);
+        assert_eq!(t.coeff(0), Rational64::from_integer(7));
+        assert_eq!(t.coeff(1), Rational64::zero());
+        assert_eq!(t.coeff(10), Rational64::zero());
+    }
+
+    #[test]
+    fn monic_divides_by_leading_coeff() {
+        assert_eq!(
+            from_integer_slice(&vec![4, 2]).monic(),
+            from_integer_slice(&vec![2, 1])
+        );
+        assert!(from_integer_slice(&vec![0]).monic().is_zero());
+    }
+
+    #[test]
+    fn monomial_and_from_roots() {
+        assert_eq!(
+            Poly::monomial(Rational64::from_integer(3), 2),
+            from_integer_slice(&vec![0, 0, 3])
+        );
+        // (x - 1)(x + 2)(x - 3) = x^3 - 2x^2 - 5x + 6
+        let roots = [1, -2, 3].map(Rational64::from_integer);
+        let t = Poly::from_roots(&roots);
+        assert_eq!(t, from_integer_slice(&vec![6, -5, -2, 1]));
+        for root in roots.iter() {
+            assert!(t.evaluate(root).is_zero());
+        }
+        assert_eq!(Poly::<Rational64>::from_roots(&[]), from_integer_slice(&vec![1]));
+    }
+
+    #[test]
+    fn coefficient_iterators() {
+        let t = from_integer_slice(&vec![7, 0, 3]);
+        let coeffs: Vec<i64> = t.coeffs().map(|x| x.to_integer()).collect();
+        assert_eq!(coeffs, vec![7, 0, 3]);
+        let terms: Vec<(usize, i64)> = t.terms().map(|(i, x)| (i, x.to_integer())).collect();
+        assert_eq!(terms, vec![(0, 7), (2, 3)]);
+    }
+
+    #[test]
+    fn map_coeffs_changes_type() {
+        let t = Poly::new(vec![1i64, 2, 3]);
+        assert_eq!(
+            t.map_coeffs(|x| Rational64::new(*x, 2)),
+            Poly::new(vec![
+                Rational64::new(1, 2),
+                Rational64::from_integer(1),
+                Rational64::new(3, 2)
+            ])
+        );
+        // coefficients that map to zero are trimmed
+        assert_eq!(t.map_coeffs(|x| x % 3), Poly::new(vec![1i64, 2]));
+    }
+
+    #[test]
+    fn reverse_coeffs() {
+        assert_eq!(
+            from_integer_slice(&vec![1, 2, 3]).reverse(),
+            from_integer_slice(&vec![3, 2, 1])
+        );
+        // a zero constant term lowers the degree
+        assert_eq!(
+            from_integer_slice(&vec![0, 2, 3]).reverse(),
+            from_integer_slice(&vec![3, 2])
+        );
+    }
+
+    #[test]
+    fn zero_and_one_are_constants() {
+        // monic polys used to count as one
+        assert!(!from_integer_slice(&vec![5, 1]).is_one());
+        assert!(!from_integer_slice(&vec![0, 1]).is_zero());
+        assert!(from_integer_slice(&vec![1, 0, 0]).is_one());
+        assert!(from_integer_slice(&vec![0, 0]).is_zero());
+    }
+
+    #[test]
+    fn borrowed_operators() {
+        let a = from_integer_slice(&vec![1, 2, 3]);
+        let b = from_integer_slice(&vec![4, 0, -3]);
+        assert_eq!(&a + &b, from_integer_slice(&vec![5, 2]));
+        assert_eq!(&a - &b, from_integer_slice(&vec![-3, 2, 6]));
+        assert_eq!(&a * &b, a.clone() * b.clone());
+        assert_eq!(&a % &b, a.modulus(&b));
+        assert_eq!(-&a, from_integer_slice(&vec![-1, -2, -3]));
+        // the operands are still usable
+        assert_eq!(a + b, from_integer_slice(&vec![5, 2]));
+    }
+
+    #[test]
+    fn assign_operators() {
+        let mut t = from_integer_slice(&vec![1, 1]);
+        t += &from_integer_slice(&vec![0, 0, 1]);
+        assert_eq!(t, from_integer_slice(&vec![1, 1, 1]));
+        t -= from_integer_slice(&vec![0, 0, 1]);
+        assert_eq!(t, from_integer_slice(&vec![1, 1]));
+        t *= &from_integer_slice(&vec![-1, 1]);
+        assert_eq!(t, from_integer_slice(&vec![-1, 0, 1]));
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let polys = vec![
+            from_integer_slice(&vec![1, 1]),
+            from_integer_slice(&vec![-1, 1]),
+            from_integer_slice(&vec![2]),
+        ];
+        assert_eq!(polys.iter().sum::<Poly<_>>(), from_integer_slice(&vec![2, 2]));
+        assert_eq!(
+            polys.iter().product::<Poly<_>>(),
+            from_integer_slice(&vec![-2, 0, 2])
+        );
+        assert_eq!(
+            polys.into_iter().product::<Poly<_>>(),
+            from_integer_slice(&vec![-2, 0, 2])
+        );
+        assert!(Vec::<Poly<Rational64>>::new()
+            .into_iter()
+            .sum::<Poly<_>>()
+            .is_zero());
+    }
 }
@@ -0,0 +1,627 @@
+use crate::{Field, Poly, Ring};
+use serde::{Deserialize, Serialize};
+
+// A dense matrix, stored row by row
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    values: Vec<T>,
+}
+
+// P * A = L * U, with L unit lower triangular and U upper triangular.
+// Row i of P * A is row permutation[i] of A.
+#[derive(Clone, PartialEq, Eq)]
+pub struct LuDecomposition<T> {
+    pub l: Matrix<T>,
+    pub u: Matrix<T>,
+    pub permutation: Vec<usize>,
+    // whether P is an odd permutation, which flips the sign of the determinant
+    pub odd: bool,
+}
+
+impl<T: Ring> Matrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix {
+            rows,
+            cols,
+            values: vec![T::zero(); rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut identity = Matrix::zeros(n, n);
+        for i in 0..n {
+            identity[(i, i)] = T::one();
+        }
+        identity
+    }
+
+    // Builds a matrix from rows of equal length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != cols) {
+            panic!("Mismatched dimensions");
+        }
+        Matrix {
+            rows: rows.len(),
+            cols,
+            values: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    // The matrix with entry f(i, j) in row i and column j
+    pub fn from_fn(rows: usize, cols: usize, f: impl Fn(usize, usize) -> T) -> Self {
+        Matrix {
+            rows,
+            cols,
+            values: (0..rows * cols).map(|k| f(k / cols, k % cols)).collect(),
+        }
+    }
+
+    // The rows are the powers 1, x, ..., x^(cols - 1) of each x, so multiplying by the
+    // coefficients of a Poly evaluates it at every x
+    pub fn vandermonde(xs: &[T], cols: usize) -> Self {
+        let mut rows = Vec::with_capacity(xs.len());
+        for x in xs.iter() {
+            let mut power = T::one();
+            let mut row = Vec::with_capacity(cols);
+            for _j in 0..cols {
+                row.push(power.clone());
+                power = power * x.clone();
+            }
+            rows.push(row);
+        }
+        Matrix {
+            rows: xs.len(),
+            cols,
+            values: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.values[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn column(&self, j: usize) -> Vec<T> {
+        (0..self.rows).map(|i| self[(i, j)].clone()).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+    }
+
+    // self * vector
+    pub fn mul_vec(&self, vector: &[T]) -> Vec<T> {
+        if vector.len() != self.cols {
+            panic!("Mismatched dimensions");
+        }
+        (0..self.rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(vector.iter())
+                    .fold(T::zero(), |sum, (a, b)| sum + a.clone() * b.clone())
+            })
+            .collect()
+    }
+
+    // Multiplies every entry by scalar
+    pub fn scale(&self, scalar: &T) -> Self {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            values: self
+                .values
+                .iter()
+                .map(|x| x.clone() * scalar.clone())
+                .collect(),
+        }
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for j in 0..self.cols {
+            self.values.swap(a * self.cols + j, b * self.cols + j);
+        }
+    }
+
+    // row target -= factor * row source
+    fn sub_row(&mut self, target: usize, source: usize, factor: &T) {
+        for j in 0..self.cols {
+            let value = self[(source, j)].clone() * factor.clone();
+            self[(target, j)] = self[(target, j)].clone() - value;
+        }
+    }
+
+    fn check_square(&self) {
+        if !self.is_square() {
+            panic!("Matrix is not square");
+        }
+    }
+}
+
+// The operations that divide entries, so need them to form a field
+impl<T: Field> Matrix<T> {
+    // The reduced row echelon form and its pivot columns, by Gauss-Jordan elimination
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        let mut reduced = self.clone();
+        let mut pivots = Vec::new();
+        for col in 0..self.cols {
+            let row = pivots.len();
+            if row == self.rows {
+                break;
+            }
+            let pivot = match (row..self.rows).find(|&i| !reduced[(i, col)].is_zero()) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+            reduced.swap_rows(row, pivot);
+            let inverse = reduced[(row, col)].inv();
+            for j in 0..self.cols {
+                reduced[(row, j)] = reduced[(row, j)].clone() * inverse.clone();
+            }
+            for i in 0..self.rows {
+                let factor = reduced[(i, col)].clone();
+                if i != row && !factor.is_zero() {
+                    reduced.sub_row(i, row, &factor);
+                }
+            }
+            pivots.push(col);
+        }
+        (reduced, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    // LU decomposition with partial pivoting. Works for singular matrices too,
+    // leaving zeros on the diagonal of U.
+    pub fn lu(&self) -> LuDecomposition<T> {
+        self.check_square();
+        let n = self.rows;
+        let mut u = self.clone();
+        let mut l = Matrix::<T>::identity(n);
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut odd = false;
+        for col in 0..n {
+            let pivot = match (col..n).find(|&i| !u[(i, col)].is_zero()) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+            if pivot != col {
+                u.swap_rows(col, pivot);
+                permutation.swap(col, pivot);
+                // the multipliers found so far move with their rows
+                for j in 0..col {
+                    let (a, b) = (l[(col, j)].clone(), l[(pivot, j)].clone());
+                    l[(col, j)] = b;
+                    l[(pivot, j)] = a;
+                }
+                odd = !odd;
+            }
+            let inverse = u[(col, col)].inv();
+            for i in col + 1..n {
+                let factor = u[(i, col)].clone() * inverse.clone();
+                if !factor.is_zero() {
+                    u.sub_row(i, col, &factor);
+                }
+                l[(i, col)] = factor;
+            }
+        }
+        LuDecomposition {
+            l,
+            u,
+            permutation,
+            odd,
+        }
+    }
+
+    pub fn determinant(&self) -> T {
+        let lu = self.lu();
+        let product = (0..self.rows).fold(T::one(), |product, i| product * lu.u[(i, i)].clone());
+        match lu.odd {
+            true => T::zero() - product,
+            false => product,
+        }
+    }
+
+    // The inverse, or None if the matrix is singular
+    pub fn inverse(&self) -> Option<Self> {
+        self.check_square();
+        let n = self.rows;
+        let augmented = Matrix::from_fn(n, 2 * n, |i, j| match j < n {
+            true => self[(i, j)].clone(),
+            false if j - n == i => T::one(),
+            false => T::zero(),
+        });
+        let (reduced, pivots) = augmented.rref();
+        // [self | I] always has rank n, and self is invertible when its pivots are all on the left
+        if !pivots.into_iter().eq(0..n) {
+            return None;
+        }
+        Some(Matrix::from_fn(n, n, |i, j| reduced[(i, n + j)].clone()))
+    }
+
+    // Some x with self * x = rhs, with the free variables zero, or None if there isn't one
+    pub fn solve(&self, rhs: &[T]) -> Option<Vec<T>> {
+        if rhs.len() != self.rows {
+            panic!("Mismatched dimensions");
+        }
+        let augmented = Matrix::from_fn(self.rows, self.cols + 1, |i, j| match j < self.cols {
+            true => self[(i, j)].clone(),
+            false => rhs[i].clone(),
+        });
+        let (reduced, pivots) = augmented.rref();
+        // a pivot in the last column is the equation 0 = 1
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+        let mut solution = vec![T::zero(); self.cols];
+        for (row, col) in pivots.into_iter().enumerate() {
+            solution[col] = reduced[(row, self.cols)].clone();
+        }
+        Some(solution)
+    }
+
+    // The companion matrix of poly made monic, whose characteristic polynomial is poly:
+    // ones below the diagonal and minus the lower coefficients in the last column
+    pub fn companion(poly: &Poly<T>) -> Self {
+        let n = match poly.degree() {
+            Some(n) if n > 0 => n,
+            _ => panic!("Companion matrices need a Poly of degree at least one"),
+        };
+        let monic = poly.monic();
+        Matrix::from_fn(n, n, |i, j| match (i, j) {
+            (i, j) if j == n - 1 => T::zero() - monic.coeff(i),
+            (i, j) if i == j + 1 => T::one(),
+            _ => T::zero(),
+        })
+    }
+
+    // det(x I - self), by reducing to upper Hessenberg form with similarity transforms
+    // and expanding along the last column, in O(n^3) field operations
+    pub fn charpoly(&self) -> Poly<T> {
+        self.check_square();
+        let n = self.rows;
+        let mut h = self.clone();
+        for col in 0..n.saturating_sub(2) {
+            let pivot = match (col + 1..n).find(|&i| !h[(i, col)].is_zero()) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+            if pivot != col + 1 {
+                h.swap_rows(pivot, col + 1);
+                h.swap_columns(pivot, col + 1);
+            }
+            let inverse = h[(col + 1, col)].inv();
+            for i in col + 2..n {
+                let factor = h[(i, col)].clone() * inverse.clone();
+                if factor.is_zero() {
+                    continue;
+                }
+                // eliminate with a row operation and undo it with a column operation
+                h.sub_row(i, col + 1, &factor);
+                for k in 0..n {
+                    let value = h[(k, i)].clone() * factor.clone();
+                    h[(k, col + 1)] = h[(k, col + 1)].clone() + value;
+                }
+            }
+        }
+        // charpolys[m] is the characteristic polynomial of the leading m x m block
+        let x = Poly::new(vec![T::zero(), T::one()]);
+        let mut charpolys = vec![Poly::new(vec![T::one()])];
+        for m in 1..=n {
+            let diagonal = Poly::new(vec![h[(m - 1, m - 1)].clone()]);
+            let mut next = (&x - &diagonal) * &charpolys[m - 1];
+            let mut subdiagonal = T::one();
+            for i in 1..m {
+                subdiagonal = subdiagonal * h[(m - i, m - i - 1)].clone();
+                let factor = h[(m - i - 1, m - 1)].clone() * subdiagonal.clone();
+                next -= &charpolys[m - i - 1].clone().coeff_mul(factor);
+            }
+            charpolys.push(next);
+        }
+        charpolys.pop().unwrap()
+    }
+
+    fn swap_columns(&mut self, a: usize, b: usize) {
+        for i in 0..self.rows {
+            self.values.swap(i * self.cols + a, i * self.cols + b);
+        }
+    }
+}
+
+impl<T> std::ops::Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        if i >= self.rows || j >= self.cols {
+            panic!("Index ({}, {}) out of range", i, j);
+        }
+        &self.values[i * self.cols + j]
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        if i >= self.rows || j >= self.cols {
+            panic!("Index ({}, {}) out of range", i, j);
+        }
+        &mut self.values[i * self.cols + j]
+    }
+}
+
+impl<T: Ring> std::ops::Add for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: &Matrix<T>) -> Self::Output {
+        if self.rows != rhs.rows || self.cols != rhs.cols {
+            panic!("Mismatched dimensions");
+        }
+        Matrix::from_fn(self.rows, self.cols, |i, j| {
+            self[(i, j)].clone() + rhs[(i, j)].clone()
+        })
+    }
+}
+
+impl<T: Ring> std::ops::Add for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: Matrix<T>) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<T: Ring> std::ops::Sub for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: &Matrix<T>) -> Self::Output {
+        if self.rows != rhs.rows || self.cols != rhs.cols {
+            panic!("Mismatched dimensions");
+        }
+        Matrix::from_fn(self.rows, self.cols, |i, j| {
+            self[(i, j)].clone() - rhs[(i, j)].clone()
+        })
+    }
+}
+
+impl<T: Ring> std::ops::Sub for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: Matrix<T>) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl<T: Ring> std::ops::Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: &Matrix<T>) -> Self::Output {
+        if self.cols != rhs.rows {
+            panic!("Mismatched dimensions");
+        }
+        let mut product = Matrix::<T>::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = &self[(i, k)];
+                if a.is_zero() {
+                    continue;
+                }
+                for j in 0..rhs.cols {
+                    product[(i, j)] = product[(i, j)].clone() + a.clone() * rhs[(k, j)].clone();
+                }
+            }
+        }
+        product
+    }
+}
+
+impl<T: Ring> std::ops::Mul for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Matrix<T>) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+// One bracketed row per line
+impl<T: std::fmt::Display> std::fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        for i in 0..self.rows {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for j in 0..self.cols {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", self.values[i * self.cols + j])?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Debug for Matrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Debug for LuDecomposition<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.debug_struct("LuDecomposition")
+            .field("l", &self.l)
+            .field("u", &self.u)
+            .field("permutation", &self.permutation)
+            .field("odd", &self.odd)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_integer_slice, interpolate, new_from_slice, GF2256};
+    use num::rational::Rational64;
+    use num::{One, Zero};
+
+    fn q(rows: &[&[i64]]) -> Matrix<Rational64> {
+        Matrix::from_rows(
+            rows.iter()
+                .map(|row| row.iter().map(|x| Rational64::from_integer(*x)).collect())
+                .collect(),
+        )
+    }
+
+    fn gf(x: u8) -> GF2256 {
+        GF2256::new(&new_from_slice(&[x]))
+    }
+
+    // p(m) by Horner's rule
+    fn evaluate_at_matrix(p: &Poly<Rational64>, m: &Matrix<Rational64>) -> Matrix<Rational64> {
+        let identity = Matrix::identity(m.nrows());
+        p.coeffs()
+            .rev()
+            .fold(Matrix::zeros(m.nrows(), m.ncols()), |sum, c| {
+                &(&sum * m) + &identity.scale(c)
+            })
+    }
+
+    #[test]
+    fn multiplication() {
+        let a = q(&[&[1, 2], &[3, 4]]);
+        let b = q(&[&[0, 1], &[1, 0]]);
+        assert_eq!(&a * &b, q(&[&[2, 1], &[4, 3]]));
+        assert_eq!(&a * &Matrix::identity(2), a);
+        assert_eq!(
+            a.mul_vec(&[Rational64::one(), Rational64::zero()]),
+            a.column(0)
+        );
+        assert_eq!(a.transpose(), q(&[&[1, 3], &[2, 4]]));
+        assert_eq!(&a - &a, Matrix::zeros(2, 2));
+        assert_eq!(format!("{}", a), "[1, 2]\n[3, 4]");
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(
+            q(&[&[2, 0, 1], &[1, 3, 2], &[1, 1, 2]]).determinant(),
+            Rational64::from_integer(6)
+        );
+        // needs a row swap
+        assert_eq!(
+            q(&[&[0, 1], &[1, 0]]).determinant(),
+            Rational64::from_integer(-1)
+        );
+        assert!(q(&[&[1, 2], &[2, 4]]).determinant().is_zero());
+        assert_eq!(
+            Matrix::<Rational64>::identity(0).determinant(),
+            Rational64::one()
+        );
+    }
+
+    #[test]
+    fn lu_reconstructs_the_matrix() {
+        let a = q(&[&[0, 2, 1], &[1, 1, 1], &[2, 1, 3]]);
+        let lu = a.lu();
+        let permuted =
+            Matrix::from_rows(lu.permutation.iter().map(|i| a.row(*i).to_vec()).collect());
+        assert_eq!(&lu.l * &lu.u, permuted);
+        for i in 0..3 {
+            assert!(lu.l[(i, i)].is_one());
+            for j in 0..i {
+                assert!(lu.u[(i, j)].is_zero());
+                assert!(lu.l[(j, i)].is_zero());
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_and_rank() {
+        let a = q(&[&[2, 1, 1], &[1, 3, 2], &[1, 0, 0]]);
+        let inverse = a.inverse().unwrap();
+        assert_eq!(&a * &inverse, Matrix::identity(3));
+        assert_eq!(a.rank(), 3);
+        let singular = q(&[&[1, 2, 3], &[2, 4, 6], &[1, 1, 1]]);
+        assert_eq!(singular.inverse(), None);
+        assert_eq!(singular.rank(), 2);
+        assert_eq!(q(&[&[1, 2, 3]]).rank(), 1);
+    }
+
+    #[test]
+    fn solve() {
+        let a = q(&[&[1, 1], &[1, -1], &[2, 0]]);
+        let b = q(&[&[3], &[1], &[4]]).column(0);
+        let x = a.solve(&b).unwrap();
+        assert_eq!(a.mul_vec(&x), b);
+        let inconsistent = q(&[&[3], &[1], &[5]]).column(0);
+        assert_eq!(a.solve(&inconsistent), None);
+    }
+
+    #[test]
+    fn gf2256_inverse() {
+        let a = Matrix::from_rows(vec![vec![gf(3), gf(7)], vec![gf(0x53), gf(1)]]);
+        let inverse = a.inverse().unwrap();
+        assert_eq!(&inverse * &a, Matrix::identity(2));
+        assert_eq!(a.determinant(), gf(3) * gf(1) - gf(7) * gf(0x53));
+    }
+
+    #[test]
+    fn vandermonde_solve_matches_interpolate() {
+        let xs: Vec<Rational64> = [-2, 0, 1, 3].map(Rational64::from_integer).to_vec();
+        let ys: Vec<Rational64> = [5, -1, 2, 7].map(Rational64::from_integer).to_vec();
+        let coeffs = Matrix::vandermonde(&xs, 4).solve(&ys).unwrap();
+        assert_eq!(Poly::new(coeffs), interpolate(&xs, &ys).unwrap());
+    }
+
+    #[test]
+    fn vandermonde_over_gf2256_matches_interpolate() {
+        let xs: Vec<GF2256> = (1..=5).map(gf).collect();
+        let ys: Vec<GF2256> = [9, 200, 31, 4, 77].map(gf).to_vec();
+        let coeffs = Matrix::vandermonde(&xs, 5).solve(&ys).unwrap();
+        assert_eq!(Poly::new(coeffs), interpolate(&xs, &ys).unwrap());
+    }
+
+    #[test]
+    fn companion_has_the_poly_as_charpoly() {
+        let p = from_integer_slice(&vec![6, -5, 0, 2]);
+        let companion = Matrix::companion(&p);
+        assert_eq!(companion.charpoly(), p.monic());
+        assert_eq!(
+            companion.determinant(),
+            p.monic().coeff(0) * Rational64::from_integer(-1)
+        );
+    }
+
+    #[test]
+    fn charpoly() {
+        let a = q(&[&[2, 1], &[1, 2]]);
+        // (x - 1)(x - 3)
+        assert_eq!(a.charpoly(), from_integer_slice(&vec![3, -4, 1]));
+        let b = q(&[&[1, 2, 0, 3], &[0, 1, 4, 1], &[2, 0, 1, 1], &[1, 1, 1, 0]]);
+        let charpoly = b.charpoly();
+        // Cayley-Hamilton, and the constant term is det(-b) = det(b) for even n
+        assert!(evaluate_at_matrix(&charpoly, &b) == Matrix::zeros(4, 4));
+        assert_eq!(charpoly.coeff(0), b.determinant());
+        assert_eq!(
+            charpoly.coeff(3),
+            -(0..4).fold(Rational64::zero(), |sum, i| sum + b[(i, i)])
+        );
+    }
+}
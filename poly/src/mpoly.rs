@@ -0,0 +1,898 @@
+use crate::{Field, Matrix, Ring};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+// Orderings of the monomials of an MPoly, with x0 > x1 > ... > x(n-1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MonomialOrder {
+    // Lexicographic: compares the exponent of x0 first, then x1, ...
+    Lex,
+    // Graded lexicographic: compares total degree first, then Lex
+    GrLex,
+    // Graded reverse lexicographic: compares total degree first, then the smaller
+    // exponent in the last variable where they differ wins
+    GrevLex,
+}
+
+impl MonomialOrder {
+    // Compares two exponent vectors of the same length
+    pub fn cmp(&self, a: &[usize], b: &[usize]) -> Ordering {
+        match self {
+            MonomialOrder::Lex => a.cmp(b),
+            MonomialOrder::GrLex => total_degree(a).cmp(&total_degree(b)).then_with(|| a.cmp(b)),
+            MonomialOrder::GrevLex => total_degree(a).cmp(&total_degree(b)).then_with(|| {
+                for i in (0..a.len()).rev() {
+                    if a[i] != b[i] {
+                        return b[i].cmp(&a[i]);
+                    }
+                }
+                Ordering::Equal
+            }),
+        }
+    }
+}
+
+fn total_degree(monomial: &[usize]) -> usize {
+    monomial.iter().sum()
+}
+
+// Whether the monomial a divides the monomial b
+fn divides(a: &[usize], b: &[usize]) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| x <= y)
+}
+
+fn lcm(a: &[usize], b: &[usize]) -> Vec<usize> {
+    a.iter().zip(b.iter()).map(|(x, y)| *x.max(y)).collect()
+}
+
+fn pow<T: Ring>(value: &T, exponent: usize) -> T {
+    let mut result = T::one();
+    for _i in 0..exponent {
+        result = result * value.clone();
+    }
+    result
+}
+
+// Struct that represents a polynomial in several variables
+// by its non-zero terms (exponents of x0..x(n-1), coefficient),
+// sorted from the leading term down in the given MonomialOrder
+#[derive(Clone, PartialEq)]
+pub struct MPoly<T> {
+    nvars: usize,
+    order: MonomialOrder,
+    terms: Vec<(Vec<usize>, T)>,
+}
+
+impl<T: Ring> MPoly<T> {
+    // Creates new MPoly from (exponents, coefficient) terms in any order,
+    // combining like terms and dropping zero ones
+    pub fn new(nvars: usize, order: MonomialOrder, terms: Vec<(Vec<usize>, T)>) -> Self {
+        let mut terms = terms;
+        for (monomial, _coeff) in terms.iter() {
+            if monomial.len() != nvars {
+                panic!("Wrong number of variables");
+            }
+        }
+        terms.sort_by(|a, b| order.cmp(&b.0, &a.0));
+        let mut combined: Vec<(Vec<usize>, T)> = Vec::new();
+        for (monomial, coeff) in terms {
+            match combined.last_mut() {
+                Some(last) if last.0 == monomial => last.1 = last.1.clone() + coeff,
+                _ => combined.push((monomial, coeff)),
+            }
+        }
+        combined.retain(|(_monomial, coeff)| !coeff.is_zero());
+        MPoly {
+            nvars,
+            order,
+            terms: combined,
+        }
+    }
+
+    pub fn zero(nvars: usize, order: MonomialOrder) -> Self {
+        MPoly::new(nvars, order, vec![])
+    }
+
+    pub fn constant(nvars: usize, order: MonomialOrder, value: T) -> Self {
+        MPoly::new(nvars, order, vec![(vec![0; nvars], value)])
+    }
+
+    // The MPoly x_index
+    pub fn variable(nvars: usize, order: MonomialOrder, index: usize) -> Self {
+        let mut monomial = vec![0; nvars];
+        monomial[index] = 1;
+        MPoly::new(nvars, order, vec![(monomial, T::one())])
+    }
+
+    pub fn nvars(&self) -> usize {
+        self.nvars
+    }
+
+    pub fn order(&self) -> MonomialOrder {
+        self.order
+    }
+
+    pub fn terms(&self) -> &[(Vec<usize>, T)] {
+        &self.terms
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    // The same MPoly with its terms sorted by a different MonomialOrder
+    pub fn with_order(&self, order: MonomialOrder) -> Self {
+        MPoly::new(self.nvars, order, self.terms.clone())
+    }
+
+    pub fn leading_term(&self) -> Option<&(Vec<usize>, T)> {
+        self.terms.first()
+    }
+
+    pub fn leading_monomial(&self) -> Option<&[usize]> {
+        self.terms
+            .first()
+            .map(|(monomial, _coeff)| monomial.as_slice())
+    }
+
+    pub fn leading_coeff(&self) -> Option<&T> {
+        self.terms.first().map(|(_monomial, coeff)| coeff)
+    }
+
+    // Highest total degree of any term, or None for the zero MPoly
+    pub fn total_degree(&self) -> Option<usize> {
+        self.terms
+            .iter()
+            .map(|(monomial, _coeff)| total_degree(monomial))
+            .max()
+    }
+
+    // Evaluates at the point (values[0], ..., values[n-1])
+    pub fn evaluate(&self, values: &[T]) -> T {
+        if values.len() != self.nvars {
+            panic!("Wrong number of variables");
+        }
+        let mut result = T::zero();
+        for (monomial, coeff) in self.terms.iter() {
+            let mut term = coeff.clone();
+            for (value, exponent) in values.iter().zip(monomial.iter()) {
+                term = term * pow(value, *exponent);
+            }
+            result = result + term;
+        }
+        result
+    }
+
+    // Replaces x_index with the given MPoly
+    pub fn substitute(&self, index: usize, value: &MPoly<T>) -> Self {
+        let mut result = MPoly::zero(self.nvars, self.order);
+        for (monomial, coeff) in self.terms.iter() {
+            let mut rest = monomial.clone();
+            rest[index] = 0;
+            let mut term = MPoly::new(self.nvars, self.order, vec![(rest, coeff.clone())]);
+            for _i in 0..monomial[index] {
+                term = term * value.clone();
+            }
+            result = result + term;
+        }
+        result
+    }
+
+    // Multiplies by a single term
+    fn mul_term(&self, monomial: &[usize], coeff: &T) -> Self {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(m, c)| {
+                let shifted = m.iter().zip(monomial.iter()).map(|(a, b)| a + b).collect();
+                (shifted, c.clone() * coeff.clone())
+            })
+            .collect();
+        MPoly::new(self.nvars, self.order, terms)
+    }
+
+    fn check_compatible(&self, rhs: &MPoly<T>) {
+        if self.nvars != rhs.nvars {
+            panic!("Mismatched number of variables");
+        }
+    }
+}
+
+// The operations that divide coefficients
+impl<T: Field> MPoly<T> {
+    // Divides every coefficient by the leading one
+    pub fn monic(&self) -> Self {
+        let lc = match self.leading_coeff() {
+            Some(lc) => lc.clone(),
+            None => return self.clone(),
+        };
+        self.mul_term(&vec![0; self.nvars], &(T::one() / lc))
+    }
+
+    // Multivariate division: returns quotients q_i and a remainder r with
+    // self = sum of q_i * divisors[i] + r, where no term of r is divisible
+    // by any leading monomial of the divisors, all taken in self's MonomialOrder
+    pub fn div(&self, divisors: &[MPoly<T>]) -> (Vec<MPoly<T>>, MPoly<T>) {
+        // leading terms in another order needn't cancel the leading term of rest
+        let divisors: Vec<MPoly<T>> = divisors
+            .iter()
+            .map(|divisor| {
+                self.check_compatible(divisor);
+                divisor.with_order(self.order)
+            })
+            .collect();
+        let mut quotients = vec![MPoly::zero(self.nvars, self.order); divisors.len()];
+        let mut remainder = Vec::new();
+        let mut rest = self.clone();
+        while let Some((monomial, coeff)) = rest.leading_term().cloned() {
+            let divisor = divisors
+                .iter()
+                .position(|divisor| match divisor.leading_term() {
+                    Some((lm, _lc)) => divides(lm, &monomial),
+                    None => false,
+                });
+            match divisor {
+                Some(i) => {
+                    let (lm, lc) = divisors[i].leading_term().unwrap();
+                    let shift: Vec<usize> =
+                        monomial.iter().zip(lm.iter()).map(|(a, b)| a - b).collect();
+                    let scale = coeff / lc.clone();
+                    rest = rest - divisors[i].mul_term(&shift, &scale);
+                    quotients[i] = quotients[i].clone()
+                        + MPoly::new(self.nvars, self.order, vec![(shift, scale)]);
+                }
+                None => {
+                    remainder.push((monomial, coeff));
+                    rest.terms.remove(0);
+                }
+            }
+        }
+        (quotients, MPoly::new(self.nvars, self.order, remainder))
+    }
+}
+
+// The S-polynomial of f and g, which cancels their leading terms
+fn s_poly<T: Field>(f: &MPoly<T>, g: &MPoly<T>) -> MPoly<T> {
+    let (f_lm, f_lc) = f.leading_term().unwrap();
+    let (g_lm, g_lc) = g.leading_term().unwrap();
+    let lcm = lcm(f_lm, g_lm);
+    let f_shift: Vec<usize> = lcm.iter().zip(f_lm.iter()).map(|(a, b)| a - b).collect();
+    let g_shift: Vec<usize> = lcm.iter().zip(g_lm.iter()).map(|(a, b)| a - b).collect();
+    f.mul_term(&f_shift, &(T::one() / f_lc.clone()))
+        - g.mul_term(&g_shift, &(T::one() / g_lc.clone()))
+}
+
+// Whether the monomials a and b share no variable, when their S-polynomial always
+// reduces to zero (Buchberger's first criterion)
+fn coprime(a: &[usize], b: &[usize]) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| *x == 0 || *y == 0)
+}
+
+// f times the monomial that takes its leading monomial to monomial
+fn multiple<T: Field>(f: &MPoly<T>, monomial: &[usize]) -> MPoly<T> {
+    let lm = f.leading_monomial().unwrap();
+    let shift: Vec<usize> = monomial.iter().zip(lm.iter()).map(|(a, b)| a - b).collect();
+    f.mul_term(&shift, &T::one())
+}
+
+// The non-zero polys, monic and in the MonomialOrder of the first, with every pair of them
+fn initial_basis<T: Field>(
+    polys: &[MPoly<T>],
+    order: MonomialOrder,
+) -> (Vec<MPoly<T>>, Vec<(usize, usize)>) {
+    let basis: Vec<MPoly<T>> = polys
+        .iter()
+        .filter(|poly| !poly.is_zero())
+        .map(|poly| poly.with_order(order).monic())
+        .collect();
+    let mut pairs = Vec::new();
+    for j in 0..basis.len() {
+        for i in 0..j {
+            pairs.push((i, j));
+        }
+    }
+    (basis, pairs)
+}
+
+// Returns the reduced Gröbner basis of the ideal generated by polys (Buchberger's algorithm),
+// in the MonomialOrder of the first MPoly: monic, sorted by leading monomial from the
+// largest down, and unique for the ideal. Empty for the zero ideal.
+pub fn groebner<T: Field>(polys: &[MPoly<T>]) -> Vec<MPoly<T>> {
+    let order = match polys.first() {
+        Some(poly) => poly.order,
+        None => return vec![],
+    };
+    let (mut basis, mut pairs) = initial_basis(polys, order);
+    while let Some((i, j)) = pairs.pop() {
+        let f_lm = basis[i].leading_monomial().unwrap();
+        let g_lm = basis[j].leading_monomial().unwrap();
+        if coprime(f_lm, g_lm) {
+            continue;
+        }
+        let remainder = s_poly(&basis[i], &basis[j]).div(&basis).1;
+        if !remainder.is_zero() {
+            for k in 0..basis.len() {
+                pairs.push((k, basis.len()));
+            }
+            basis.push(remainder.monic());
+        }
+    }
+    reduce_basis(basis, order)
+}
+
+// The same reduced Gröbner basis as groebner, by Faugère's F4: each round takes the
+// S-pairs whose lcm has the lowest degree and reduces them all at once, as the rows of
+// one Macaulay matrix put in reduced row echelon form
+pub fn groebner_f4<T: Field>(polys: &[MPoly<T>]) -> Vec<MPoly<T>> {
+    let order = match polys.first() {
+        Some(poly) => poly.order,
+        None => return vec![],
+    };
+    let nvars = polys[0].nvars;
+    let (mut basis, mut pairs) = initial_basis(polys, order);
+    loop {
+        pairs.retain(|(i, j)| {
+            !coprime(
+                basis[*i].leading_monomial().unwrap(),
+                basis[*j].leading_monomial().unwrap(),
+            )
+        });
+        let pair_lcm = |(i, j): &(usize, usize)| {
+            lcm(
+                basis[*i].leading_monomial().unwrap(),
+                basis[*j].leading_monomial().unwrap(),
+            )
+        };
+        let degree = match pairs.iter().map(|pair| total_degree(&pair_lcm(pair))).min() {
+            Some(degree) => degree,
+            None => break,
+        };
+        let (selected, rest): (Vec<_>, Vec<_>) = pairs
+            .iter()
+            .partition(|pair| total_degree(&pair_lcm(pair)) == degree);
+        // both halves of each S-polynomial, as multiples of basis elements
+        let mut rows: Vec<MPoly<T>> = Vec::new();
+        for (i, j) in selected {
+            let lcm = pair_lcm(&(i, j));
+            for k in [i, j] {
+                let row = multiple(&basis[k], &lcm);
+                if !rows.contains(&row) {
+                    rows.push(row);
+                }
+            }
+        }
+        pairs = rest;
+        // symbolic preprocessing: a row to cancel every other monomial that some
+        // leading monomial of the basis divides
+        let mut covered: HashSet<Vec<usize>> = rows
+            .iter()
+            .map(|row| row.leading_monomial().unwrap().to_vec())
+            .collect();
+        let mut next = 0;
+        while next < rows.len() {
+            let monomials: Vec<Vec<usize>> = rows[next]
+                .terms
+                .iter()
+                .map(|(monomial, _coeff)| monomial.clone())
+                .collect();
+            for monomial in monomials {
+                if !covered.insert(monomial.clone()) {
+                    continue;
+                }
+                if let Some(reducer) = basis
+                    .iter()
+                    .find(|g| divides(g.leading_monomial().unwrap(), &monomial))
+                {
+                    rows.push(multiple(reducer, &monomial));
+                }
+            }
+            next += 1;
+        }
+        let leading: HashSet<&[usize]> = rows
+            .iter()
+            .map(|row| row.leading_monomial().unwrap())
+            .collect();
+        // one column per monomial, from the largest down
+        let mut columns: Vec<Vec<usize>> = covered.into_iter().collect();
+        columns.sort_by(|a, b| order.cmp(b, a));
+        let index: HashMap<&[usize], usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(j, monomial)| (monomial.as_slice(), j))
+            .collect();
+        let matrix = Matrix::from_rows(
+            rows.iter()
+                .map(|row| {
+                    let mut entries = vec![T::zero(); columns.len()];
+                    for (monomial, coeff) in row.terms.iter() {
+                        entries[index[monomial.as_slice()]] = coeff.clone();
+                    }
+                    entries
+                })
+                .collect(),
+        );
+        let (reduced, pivots) = matrix.rref();
+        // the rows with a leading monomial none of the rows had are new to the basis
+        for (i, pivot) in pivots.iter().enumerate() {
+            if leading.contains(columns[*pivot].as_slice()) {
+                continue;
+            }
+            let terms = (*pivot..columns.len())
+                .map(|j| (columns[j].clone(), reduced[(i, j)].clone()))
+                .collect();
+            for k in 0..basis.len() {
+                pairs.push((k, basis.len()));
+            }
+            basis.push(MPoly::new(nvars, order, terms));
+        }
+    }
+    reduce_basis(basis, order)
+}
+
+// The reduced Gröbner basis from any Gröbner basis of the ideal
+fn reduce_basis<T: Field>(basis: Vec<MPoly<T>>, order: MonomialOrder) -> Vec<MPoly<T>> {
+    // minimal: drop every element whose leading monomial is divisible by another's
+    let mut minimal: Vec<MPoly<T>> = Vec::new();
+    for i in 0..basis.len() {
+        let lm = basis[i].leading_monomial().unwrap();
+        let redundant = (0..basis.len()).any(|j| {
+            let other = basis[j].leading_monomial().unwrap();
+            j != i && divides(other, lm) && (other != lm || j < i)
+        });
+        if !redundant {
+            minimal.push(basis[i].clone());
+        }
+    }
+    // reduced: no term of any element is divisible by another's leading monomial
+    for i in 0..minimal.len() {
+        let others: Vec<MPoly<T>> = minimal
+            .iter()
+            .enumerate()
+            .filter(|(j, _poly)| *j != i)
+            .map(|(_j, poly)| poly.clone())
+            .collect();
+        minimal[i] = minimal[i].div(&others).1.monic();
+    }
+    minimal.sort_by(|a, b| order.cmp(b.leading_monomial().unwrap(), a.leading_monomial().unwrap()));
+    minimal
+}
+
+impl<T: Ring> std::ops::Add for MPoly<T> {
+    type Output = MPoly<T>;
+
+    fn add(self, rhs: MPoly<T>) -> Self {
+        self.check_compatible(&rhs);
+        let mut terms = self.terms;
+        terms.extend(rhs.terms);
+        MPoly::new(self.nvars, self.order, terms)
+    }
+}
+
+impl<T: Ring> std::ops::Sub for MPoly<T> {
+    type Output = MPoly<T>;
+
+    fn sub(self, rhs: MPoly<T>) -> Self {
+        self.check_compatible(&rhs);
+        let mut terms = self.terms;
+        terms.extend(
+            rhs.terms
+                .into_iter()
+                .map(|(monomial, coeff)| (monomial, T::zero() - coeff)),
+        );
+        MPoly::new(self.nvars, self.order, terms)
+    }
+}
+
+impl<T: Ring> std::ops::Mul for MPoly<T> {
+    type Output = MPoly<T>;
+
+    fn mul(self, rhs: MPoly<T>) -> Self {
+        self.check_compatible(&rhs);
+        let mut terms = Vec::with_capacity(self.terms.len() * rhs.terms.len());
+        for (m1, c1) in self.terms.iter() {
+            for (m2, c2) in rhs.terms.iter() {
+                let monomial = m1.iter().zip(m2.iter()).map(|(a, b)| a + b).collect();
+                terms.push((monomial, c1.clone() * c2.clone()));
+            }
+        }
+        MPoly::new(self.nvars, self.order, terms)
+    }
+}
+
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Display
+    for MPoly<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        if self.terms.is_empty() {
+            return write!(f, "{}", T::zero());
+        }
+        for (i, (monomial, coeff)) in self.terms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            let variables: Vec<String> = monomial
+                .iter()
+                .enumerate()
+                .filter(|(_var, exponent)| **exponent > 0)
+                .map(|(var, exponent)| match exponent {
+                    1 => format!("x{}", var),
+                    _ => format!("x{}^{}", var, exponent),
+                })
+                .collect();
+            if variables.is_empty() {
+                write!(f, "{}", coeff)?;
+            } else if coeff.is_one() {
+                write!(f, "{}", variables.join("*"))?;
+            } else {
+                write!(f, "{}{}", coeff, variables.join("*"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Debug for MPoly<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{new_from_slice, GF2256};
+    use num::rational::Rational64;
+    use num::{One, Zero};
+    use z2z::Z2z;
+
+    fn q(terms: &[(&[usize], i64, i64)], order: MonomialOrder) -> MPoly<Rational64> {
+        let nvars = terms[0].0.len();
+        MPoly::new(
+            nvars,
+            order,
+            terms
+                .iter()
+                .map(|&(m, n, d)| (m.to_vec(), Rational64::new(n, d)))
+                .collect(),
+        )
+    }
+
+    fn z(terms: &[&[usize]]) -> MPoly<Z2z> {
+        MPoly::new(
+            terms[0].len(),
+            MonomialOrder::Lex,
+            terms.iter().map(|m| (m.to_vec(), Z2z::One)).collect(),
+        )
+    }
+
+    #[test]
+    fn orderings() {
+        // examples from Cox, Little and O'Shea
+        let a = [1, 2, 0];
+        let b = [0, 3, 4];
+        assert_eq!(MonomialOrder::Lex.cmp(&a, &b), Ordering::Greater);
+        assert_eq!(MonomialOrder::GrLex.cmp(&a, &b), Ordering::Less);
+        let a = [1, 2, 3];
+        let b = [3, 2, 0];
+        assert_eq!(MonomialOrder::GrLex.cmp(&a, &b), Ordering::Greater);
+        let a = [4, 7, 1];
+        let b = [4, 2, 3];
+        assert_eq!(MonomialOrder::GrevLex.cmp(&a, &b), Ordering::Greater);
+        let a = [1, 5, 2];
+        let b = [4, 1, 3];
+        assert_eq!(MonomialOrder::GrevLex.cmp(&a, &b), Ordering::Greater);
+        assert_eq!(MonomialOrder::GrLex.cmp(&a, &b), Ordering::Less);
+    }
+
+    #[test]
+    fn new_combines_and_sorts() {
+        let t = q(
+            &[
+                (&[0, 1], 1, 1),
+                (&[2, 0], 3, 1),
+                (&[0, 1], 2, 1),
+                (&[1, 1], 1, 1),
+                (&[1, 1], -1, 1),
+            ],
+            MonomialOrder::Lex,
+        );
+        assert_eq!(
+            t.terms(),
+            &[
+                (vec![2, 0], Rational64::from_integer(3)),
+                (vec![0, 1], Rational64::from_integer(3))
+            ]
+        );
+        assert_eq!(t.total_degree(), Some(2));
+        assert_eq!(t.leading_monomial(), Some(&[2, 0][..]));
+    }
+
+    #[test]
+    fn with_order_changes_leading_term() {
+        let t = q(&[(&[1, 0], 1, 1), (&[0, 2], 1, 1)], MonomialOrder::Lex);
+        assert_eq!(t.leading_monomial(), Some(&[1, 0][..]));
+        let t = t.with_order(MonomialOrder::GrLex);
+        assert_eq!(t.leading_monomial(), Some(&[0, 2][..]));
+    }
+
+    #[test]
+    fn add_sub_mul() {
+        let x = MPoly::<Rational64>::variable(2, MonomialOrder::GrLex, 0);
+        let y = MPoly::<Rational64>::variable(2, MonomialOrder::GrLex, 1);
+        let one = MPoly::constant(2, MonomialOrder::GrLex, Rational64::one());
+        // (x + y)(x - y) = x^2 - y^2
+        assert_eq!(
+            (x.clone() + y.clone()) * (x.clone() - y.clone()),
+            q(&[(&[2, 0], 1, 1), (&[0, 2], -1, 1)], MonomialOrder::GrLex)
+        );
+        assert!((x.clone() - x.clone()).is_zero());
+        assert_eq!(
+            (x.clone() + one.clone()) * (y.clone() + one.clone()),
+            q(
+                &[
+                    (&[1, 1], 1, 1),
+                    (&[1, 0], 1, 1),
+                    (&[0, 1], 1, 1),
+                    (&[0, 0], 1, 1)
+                ],
+                MonomialOrder::GrLex
+            )
+        );
+    }
+
+    #[test]
+    fn evaluate_and_substitute() {
+        // x^2 y + 3 y - 1
+        let t = q(
+            &[(&[2, 1], 1, 1), (&[0, 1], 3, 1), (&[0, 0], -1, 1)],
+            MonomialOrder::Lex,
+        );
+        assert_eq!(
+            t.evaluate(&[Rational64::from_integer(2), Rational64::new(1, 2)]),
+            Rational64::new(5, 2)
+        );
+        // x -> y + 1 gives y^3 + 2y^2 + 4y - 1
+        let y_plus_one = q(&[(&[0, 1], 1, 1), (&[0, 0], 1, 1)], MonomialOrder::Lex);
+        assert_eq!(
+            t.substitute(0, &y_plus_one),
+            q(
+                &[
+                    (&[0, 3], 1, 1),
+                    (&[0, 2], 2, 1),
+                    (&[0, 1], 4, 1),
+                    (&[0, 0], -1, 1)
+                ],
+                MonomialOrder::Lex
+            )
+        );
+    }
+
+    #[test]
+    fn division() {
+        // x^2 y + x y^2 + y^2 divided by x y - 1 and y^2 - 1 in lex
+        let f = q(
+            &[(&[2, 1], 1, 1), (&[1, 2], 1, 1), (&[0, 2], 1, 1)],
+            MonomialOrder::Lex,
+        );
+        let f1 = q(&[(&[1, 1], 1, 1), (&[0, 0], -1, 1)], MonomialOrder::Lex);
+        let f2 = q(&[(&[0, 2], 1, 1), (&[0, 0], -1, 1)], MonomialOrder::Lex);
+        let (quotients, remainder) = f.div(&[f1.clone(), f2.clone()]);
+        assert_eq!(
+            quotients[0],
+            q(&[(&[1, 0], 1, 1), (&[0, 1], 1, 1)], MonomialOrder::Lex)
+        );
+        assert_eq!(quotients[1], q(&[(&[0, 0], 1, 1)], MonomialOrder::Lex));
+        assert_eq!(
+            remainder,
+            q(
+                &[(&[1, 0], 1, 1), (&[0, 1], 1, 1), (&[0, 0], 1, 1)],
+                MonomialOrder::Lex
+            )
+        );
+        assert_eq!(
+            quotients[0].clone() * f1 + quotients[1].clone() * f2 + remainder,
+            f
+        );
+    }
+
+    #[test]
+    fn division_uses_the_dividend_order() {
+        // in grlex x + y^2 leads with y^2, which would cancel y^2 and leave -x
+        let f = q(&[(&[0, 2], 1, 1)], MonomialOrder::Lex);
+        let g = q(&[(&[1, 0], 1, 1), (&[0, 2], 1, 1)], MonomialOrder::GrLex);
+        let (quotients, remainder) = f.div(std::slice::from_ref(&g));
+        assert_eq!(remainder, f);
+        assert!(quotients[0].is_zero());
+        assert_eq!(quotients[0].order(), MonomialOrder::Lex);
+        // and the same the other way round
+        let (quotients, remainder) = g.div(&[f]);
+        assert_eq!(remainder, q(&[(&[1, 0], 1, 1)], MonomialOrder::GrLex));
+        assert_eq!(quotients[0], q(&[(&[0, 0], 1, 1)], MonomialOrder::GrLex));
+    }
+
+    #[test]
+    fn groebner_grlex() {
+        // x^3 - 2xy and x^2 y - 2y^2 + x give x^2, xy, y^2 - x/2
+        let f1 = q(&[(&[3, 0], 1, 1), (&[1, 1], -2, 1)], MonomialOrder::GrLex);
+        let f2 = q(
+            &[(&[2, 1], 1, 1), (&[0, 2], -2, 1), (&[1, 0], 1, 1)],
+            MonomialOrder::GrLex,
+        );
+        assert_eq!(
+            groebner(&[f1, f2]),
+            vec![
+                q(&[(&[2, 0], 1, 1)], MonomialOrder::GrLex),
+                q(&[(&[1, 1], 1, 1)], MonomialOrder::GrLex),
+                q(&[(&[0, 2], 1, 1), (&[1, 0], -1, 2)], MonomialOrder::GrLex),
+            ]
+        );
+    }
+
+    #[test]
+    fn groebner_lex_eliminates() {
+        // x^2 + y^2 - 1 and x - y: lex eliminates x, leaving 2y^2 - 1
+        let f1 = q(
+            &[(&[2, 0], 1, 1), (&[0, 2], 1, 1), (&[0, 0], -1, 1)],
+            MonomialOrder::Lex,
+        );
+        let f2 = q(&[(&[1, 0], 1, 1), (&[0, 1], -1, 1)], MonomialOrder::Lex);
+        assert_eq!(
+            groebner(&[f1, f2]),
+            vec![
+                q(&[(&[1, 0], 1, 1), (&[0, 1], -1, 1)], MonomialOrder::Lex),
+                q(&[(&[0, 2], 1, 1), (&[0, 0], -1, 2)], MonomialOrder::Lex),
+            ]
+        );
+    }
+
+    #[test]
+    fn groebner_inconsistent_system() {
+        let f1 = q(&[(&[1, 1], 1, 1), (&[0, 0], -1, 1)], MonomialOrder::GrevLex);
+        let f2 = q(&[(&[0, 1], 1, 1)], MonomialOrder::GrevLex);
+        assert_eq!(
+            groebner(&[f1, f2]),
+            vec![q(&[(&[0, 0], 1, 1)], MonomialOrder::GrevLex)]
+        );
+        assert!(groebner::<Rational64>(&[]).is_empty());
+    }
+
+    #[test]
+    fn groebner_solves_z2z_system() {
+        // xy + z = 1, x + y = 1, yz = 0 with the field equations v^2 = v
+        // has the single solution x = 1, y = 0, z = 1
+        let system = vec![
+            z(&[&[1, 1, 0], &[0, 0, 1], &[0, 0, 0]]),
+            z(&[&[1, 0, 0], &[0, 1, 0], &[0, 0, 0]]),
+            z(&[&[0, 1, 1]]),
+            z(&[&[2, 0, 0], &[1, 0, 0]]),
+            z(&[&[0, 2, 0], &[0, 1, 0]]),
+            z(&[&[0, 0, 2], &[0, 0, 1]]),
+        ];
+        assert_eq!(
+            groebner(&system),
+            vec![
+                z(&[&[1, 0, 0], &[0, 0, 0]]),
+                z(&[&[0, 1, 0]]),
+                z(&[&[0, 0, 1], &[0, 0, 0]]),
+            ]
+        );
+    }
+
+    #[test]
+    fn groebner_gf2256_linear_system() {
+        // x0 + g x1 = 0 and x1 + g = 0 give x0 = g^2
+        let g = GF2256::new(&new_from_slice(&[2]));
+        let f1 = MPoly::new(
+            2,
+            MonomialOrder::Lex,
+            vec![(vec![1, 0], GF2256::one()), (vec![0, 1], g.clone())],
+        );
+        let f2 = MPoly::new(
+            2,
+            MonomialOrder::Lex,
+            vec![(vec![0, 1], GF2256::one()), (vec![0, 0], g.clone())],
+        );
+        let basis = groebner(&[f1, f2]);
+        assert_eq!(
+            basis,
+            vec![
+                MPoly::new(
+                    2,
+                    MonomialOrder::Lex,
+                    vec![
+                        (vec![1, 0], GF2256::one()),
+                        (vec![0, 0], g.clone() * g.clone())
+                    ]
+                ),
+                MPoly::new(
+                    2,
+                    MonomialOrder::Lex,
+                    vec![(vec![0, 1], GF2256::one()), (vec![0, 0], g.clone())]
+                ),
+            ]
+        );
+        assert!(basis[0].evaluate(&[g.clone() * g.clone(), g]).is_zero());
+    }
+
+    #[test]
+    fn f4_matches_buchberger() {
+        let systems = vec![
+            vec![
+                q(&[(&[3, 0], 1, 1), (&[1, 1], -2, 1)], MonomialOrder::GrLex),
+                q(
+                    &[(&[2, 1], 1, 1), (&[0, 2], -2, 1), (&[1, 0], 1, 1)],
+                    MonomialOrder::GrLex,
+                ),
+            ],
+            vec![
+                q(
+                    &[(&[2, 0], 1, 1), (&[0, 2], 1, 1), (&[0, 0], -1, 1)],
+                    MonomialOrder::Lex,
+                ),
+                q(&[(&[1, 0], 1, 1), (&[0, 1], -1, 1)], MonomialOrder::Lex),
+            ],
+            vec![
+                q(&[(&[1, 1], 1, 1), (&[0, 0], -1, 1)], MonomialOrder::GrevLex),
+                q(&[(&[0, 1], 1, 1)], MonomialOrder::GrevLex),
+            ],
+        ];
+        for system in systems {
+            assert_eq!(groebner_f4(&system), groebner(&system));
+        }
+        // cyclic-3: x + y + z, xy + yz + zx, xyz - 1, in every order
+        for order in [
+            MonomialOrder::Lex,
+            MonomialOrder::GrLex,
+            MonomialOrder::GrevLex,
+        ] {
+            let system = vec![
+                q(
+                    &[(&[1, 0, 0], 1, 1), (&[0, 1, 0], 1, 1), (&[0, 0, 1], 1, 1)],
+                    order,
+                ),
+                q(
+                    &[(&[1, 1, 0], 1, 1), (&[0, 1, 1], 1, 1), (&[1, 0, 1], 1, 1)],
+                    order,
+                ),
+                q(&[(&[1, 1, 1], 1, 1), (&[0, 0, 0], -1, 1)], order),
+            ];
+            let basis = groebner_f4(&system);
+            assert_eq!(basis, groebner(&system));
+            // z^3 = 1 is the elimination ideal
+            if order == MonomialOrder::Lex {
+                assert_eq!(
+                    basis.last(),
+                    Some(&q(&[(&[0, 0, 3], 1, 1), (&[0, 0, 0], -1, 1)], order))
+                );
+            }
+        }
+        assert!(groebner_f4::<Rational64>(&[]).is_empty());
+    }
+
+    #[test]
+    fn f4_solves_z2z_system() {
+        let system = vec![
+            z(&[&[1, 1, 0], &[0, 0, 1], &[0, 0, 0]]),
+            z(&[&[1, 0, 0], &[0, 1, 0], &[0, 0, 0]]),
+            z(&[&[0, 1, 1]]),
+            z(&[&[2, 0, 0], &[1, 0, 0]]),
+            z(&[&[0, 2, 0], &[0, 1, 0]]),
+            z(&[&[0, 0, 2], &[0, 0, 1]]),
+        ];
+        assert_eq!(groebner_f4(&system), groebner(&system));
+    }
+
+    #[test]
+    fn display() {
+        let t = q(
+            &[(&[2, 1], 3, 1), (&[0, 1], 1, 1), (&[0, 0], -1, 1)],
+            MonomialOrder::Lex,
+        );
+        assert_eq!(format!("{}", t), "3x0^2*x1 + x1 + -1");
+        assert_eq!(
+            format!("{}", MPoly::<Rational64>::zero(2, MonomialOrder::Lex)),
+            "0"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use crate::Poly;
+use crate::Ring;
+use std::str::FromStr;
+
+// The highest exponent the parser accepts, since a term x^n allocates n + 1 coefficients
+pub const MAX_PARSE_DEGREE: usize = 1 << 20;
+
+// Error from parsing a Poly, with the character position where parsing failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsePolyError {
+    pub position: usize,
+    pub kind: ParsePolyErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParsePolyErrorKind {
+    Empty,
+    UnexpectedEnd,
+    UnexpectedCharacter(char),
+    UnknownVariable(String),
+    InvalidCoefficient(String),
+    InvalidExponent(String),
+}
+
+impl std::fmt::Display for ParsePolyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &self.kind {
+            ParsePolyErrorKind::Empty => write!(f, "empty polynomial")?,
+            ParsePolyErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParsePolyErrorKind::UnexpectedCharacter(c) => {
+                write!(f, "unexpected character '{}'", c)?
+            }
+            ParsePolyErrorKind::UnknownVariable(name) => write!(f, "unknown variable '{}'", name)?,
+            ParsePolyErrorKind::InvalidCoefficient(text) => {
+                write!(f, "invalid coefficient '{}'", text)?
+            }
+            ParsePolyErrorKind::InvalidExponent(text) => write!(f, "invalid exponent '{}'", text)?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl std::error::Error for ParsePolyError {}
+
+impl<T: Ring + FromStr> Poly<T> {
+    // Parses a sum of terms like "3x^4 + x - 1/2" in the given variable.
+    // Terms can come in any order and repeat, coefficients can be implicit,
+    // signed, rational, separated from the variable by '*', or parenthesized.
+    pub fn from_str_with_variable(s: &str, variable: &str) -> Result<Poly<T>, ParsePolyError> {
+        Parser::new(s, variable).parse()
+    }
+}
+
+impl<T: Ring + FromStr> FromStr for Poly<T> {
+    type Err = ParsePolyError;
+
+    // Parses the output of Display, or anything else from_str_with_variable accepts, in x
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Poly::from_str_with_variable(s, "x")
+    }
+}
+
+struct Parser<'a> {
+    chars: Vec<char>,
+    position: usize,
+    variable: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &str, variable: &'a str) -> Self {
+        Parser {
+            chars: s.chars().collect(),
+            position: 0,
+            variable,
+        }
+    }
+
+    fn error(&self, position: usize, kind: ParsePolyErrorKind) -> ParsePolyError {
+        ParsePolyError { position, kind }
+    }
+
+    // Error for whatever is at the current position
+    fn unexpected(&mut self) -> ParsePolyError {
+        match self.peek() {
+            Some(c) => self.error(self.position, ParsePolyErrorKind::UnexpectedCharacter(c)),
+            None => self.error(self.position, ParsePolyErrorKind::UnexpectedEnd),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.position < self.chars.len() && self.chars[self.position].is_whitespace() {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.get(self.position).copied()
+    }
+
+    // Takes the longest run of characters matching the predicate
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let start = self.position;
+        while self.position < self.chars.len() && predicate(self.chars[self.position]) {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    fn parse<T: Ring + FromStr>(&mut self) -> Result<Poly<T>, ParsePolyError> {
+        if self.peek().is_none() {
+            return Err(self.error(self.position, ParsePolyErrorKind::Empty));
+        }
+        let mut values: Vec<T> = Vec::new();
+        let mut first = true;
+        while let Some(c) = self.peek() {
+            let mut negative = false;
+            if !first {
+                match c {
+                    '+' => (),
+                    '-' => negative = true,
+                    _ => return Err(self.unexpected()),
+                }
+                self.position += 1;
+            }
+            // a term can carry its own sign, as in Display's "x + -1/2"
+            match self.peek() {
+                Some('-') => {
+                    negative = !negative;
+                    self.position += 1;
+                }
+                Some('+') => self.position += 1,
+                _ => (),
+            }
+            let (coeff, exponent) = self.parse_term::<T>()?;
+            // parse_term bounds the exponent, so this can't overflow
+            let len = exponent.checked_add(1).unwrap();
+            if values.len() < len {
+                values.resize(len, T::zero());
+            }
+            values[exponent] = match negative {
+                true => values[exponent].clone() - coeff,
+                false => values[exponent].clone() + coeff,
+            };
+            first = false;
+        }
+        Ok(Poly::new(values))
+    }
+
+    // Parses [coefficient ['*']] [variable ['^' exponent]]
+    fn parse_term<T: Ring + FromStr>(&mut self) -> Result<(T, usize), ParsePolyError> {
+        let coeff = match self.peek() {
+            None => return Err(self.unexpected()),
+            Some('(') => {
+                let start = self.position;
+                self.position += 1;
+                let text = self.take_while(|c| c != ')');
+                if self.position == self.chars.len() {
+                    return Err(self.error(self.position, ParsePolyErrorKind::UnexpectedEnd));
+                }
+                self.position += 1;
+                Some(self.parse_coefficient(text.trim(), start + 1)?)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.position;
+                let text = self.take_while(|c| c.is_ascii_digit() || c == '/' || c == '.');
+                Some(self.parse_coefficient(&text, start)?)
+            }
+            _ => None,
+        };
+        let has_star = self.peek() == Some('*');
+        if has_star {
+            self.position += 1;
+        }
+        let has_variable = match self.peek() {
+            Some(c) if c.is_alphabetic() => true,
+            _ if has_star => return Err(self.unexpected()),
+            _ => false,
+        };
+        if !has_variable {
+            return match coeff {
+                Some(coeff) => Ok((coeff, 0)),
+                None => Err(self.unexpected()),
+            };
+        }
+        let start = self.position;
+        let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+        if name != self.variable {
+            return Err(self.error(start, ParsePolyErrorKind::UnknownVariable(name)));
+        }
+        let mut exponent = 1;
+        if self.peek() == Some('^') {
+            self.position += 1;
+            self.skip_whitespace();
+            let start = self.position;
+            let text = self.take_while(|c| c.is_ascii_digit());
+            exponent = text
+                .parse::<usize>()
+                .ok()
+                .filter(|exponent| *exponent <= MAX_PARSE_DEGREE)
+                .ok_or_else(|| {
+                    self.error(start, ParsePolyErrorKind::InvalidExponent(text.clone()))
+                })?;
+        }
+        Ok((coeff.unwrap_or_else(T::one), exponent))
+    }
+
+    fn parse_coefficient<T: FromStr>(&self, text: &str, start: usize) -> Result<T, ParsePolyError> {
+        text.parse().map_err(|_| {
+            self.error(
+                start,
+                ParsePolyErrorKind::InvalidCoefficient(text.to_string()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_integer_slice;
+    use num::rational::Rational64;
+
+    fn parse(s: &str) -> Result<Poly<Rational64>, ParsePolyError> {
+        s.parse()
+    }
+
+    #[test]
+    fn parse_display_output() {
+        let t = Poly::new(vec![
+            Rational64::new(1, 2),
+            Rational64::from_integer(1),
+            Rational64::from_integer(0),
+            Rational64::new(-2, 3),
+            Rational64::from_integer(3),
+        ]);
+        assert_eq!(format!("{}", t), "3x^4 + -2/3x^3 + x + 1/2");
+        assert_eq!(parse(&format!("{}", t)), Ok(t));
+    }
+
+    #[test]
+    fn parse_any_order_and_repeats() {
+        assert_eq!(
+            parse("1 + x^2 - 3*x + x^2"),
+            Ok(from_integer_slice(&vec![1, -3, 2]))
+        );
+    }
+
+    #[test]
+    fn parse_signs_and_implicit_coefficients() {
+        assert_eq!(
+            parse("-x^3 - x"),
+            Ok(from_integer_slice(&vec![0, -1, 0, -1]))
+        );
+        assert_eq!(parse("x - -2"), Ok(from_integer_slice(&vec![2, 1])));
+        assert_eq!(
+            parse("(-1/2) * x"),
+            Ok(Poly::new(vec![
+                Rational64::from_integer(0),
+                Rational64::new(-1, 2)
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_constants() {
+        assert_eq!(parse("0"), Ok(from_integer_slice(&vec![0])));
+        assert_eq!(parse(" 7/3 "), Ok(Poly::new(vec![Rational64::new(7, 3)])));
+    }
+
+    #[test]
+    fn parse_other_variable() {
+        assert_eq!(
+            Poly::<Rational64>::from_str_with_variable("2t^2 + t", "t"),
+            Ok(from_integer_slice(&vec![0, 1, 2]))
+        );
+        assert_eq!(
+            Poly::<Rational64>::from_str_with_variable("2x", "t"),
+            Err(ParsePolyError {
+                position: 1,
+                kind: ParsePolyErrorKind::UnknownVariable("x".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn parse_bounds_the_degree() {
+        assert_eq!(
+            parse("x^4000000000"),
+            Err(ParsePolyError {
+                position: 2,
+                kind: ParsePolyErrorKind::InvalidExponent("4000000000".to_string())
+            })
+        );
+        let highest = format!("x^{}", MAX_PARSE_DEGREE);
+        assert_eq!(parse(&highest).unwrap().degree(), Some(MAX_PARSE_DEGREE));
+        assert!(parse(&format!("x^{}", MAX_PARSE_DEGREE + 1)).is_err());
+    }
+
+    #[test]
+    fn parse_over_integers() {
+        assert_eq!("4x^2 - 1".parse(), Ok(Poly::new(vec![-1i64, 0, 4])));
+    }
+
+    #[test]
+    fn parse_errors_have_positions() {
+        assert_eq!(
+            parse(""),
+            Err(ParsePolyError {
+                position: 0,
+                kind: ParsePolyErrorKind::Empty
+            })
+        );
+        assert_eq!(
+            parse("x +"),
+            Err(ParsePolyError {
+                position: 3,
+                kind: ParsePolyErrorKind::UnexpectedEnd
+            })
+        );
+        assert_eq!(
+            parse("3x^ + 1"),
+            Err(ParsePolyError {
+                position: 4,
+                kind: ParsePolyErrorKind::InvalidExponent("".to_string())
+            })
+        );
+        assert_eq!(
+            parse("1/0x"),
+            Err(ParsePolyError {
+                position: 0,
+                kind: ParsePolyErrorKind::InvalidCoefficient("1/0".to_string())
+            })
+        );
+        assert_eq!(
+            parse("2x ? 1"),
+            Err(ParsePolyError {
+                position: 3,
+                kind: ParsePolyErrorKind::UnexpectedCharacter('?')
+            })
+        );
+        assert_eq!(
+            parse("1 + x^18446744073709551615"),
+            Err(ParsePolyError {
+                position: 6,
+                kind: ParsePolyErrorKind::InvalidExponent("18446744073709551615".to_string())
+            })
+        );
+        assert_eq!(
+            format!("{}", parse("2y").unwrap_err()),
+            "unknown variable 'y' at position 1"
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use crate::Poly;
+use crate::{Field, Ring};
+
+// Struct that represents a truncated formal power series
+// c_0 + c_1 x + ... + c_(precision - 1) x^(precision - 1) + O(x^precision)
+#[derive(Clone, PartialEq)]
+pub struct PowerSeries<T> {
+    coeffs: Poly<T>,
+    precision: usize,
+}
+
+impl<T: Ring> PowerSeries<T> {
+    // Creates a new PowerSeries from a Poly, dropping every term at or above x^precision
+    pub fn new(poly: Poly<T>, precision: usize) -> Self {
+        PowerSeries {
+            coeffs: truncate(poly, precision),
+            precision,
+        }
+    }
+
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    // returns the known coefficients as a Poly
+    pub fn to_poly(&self) -> Poly<T> {
+        self.coeffs.clone()
+    }
+
+    // returns the coefficient of x^i, or None if it is beyond the precision
+    pub fn coeff(&self, i: usize) -> Option<T> {
+        if i >= self.precision {
+            return None;
+        }
+        Some(self.coeffs.coeff(i))
+    }
+
+    // Same series with a smaller precision
+    pub fn truncate(&self, precision: usize) -> Self {
+        PowerSeries::new(self.coeffs.clone(), precision.min(self.precision))
+    }
+
+    // Formal derivative; the top term is lost, so precision drops by one
+    pub fn derivative(&self) -> Self {
+        let precision = self.precision.saturating_sub(1);
+        PowerSeries::new(self.coeffs.derivative(), precision)
+    }
+
+    // Returns self(inner(x)) using Horner's rule.
+    // None if inner has a non-zero constant term, since then every
+    // coefficient of the result would depend on infinitely many terms of self.
+    pub fn compose(&self, inner: &PowerSeries<T>) -> Option<Self> {
+        if !inner.coeffs.coeff(0).is_zero() {
+            return None;
+        }
+        let precision = self.precision.min(inner.precision);
+        let mut result = Poly::new(vec![T::zero()]);
+        for x in self.coeffs.coeffs().take(precision).rev() {
+            result =
+                truncate(result * inner.coeffs.clone(), precision) + Poly::new(vec![x.clone()]);
+        }
+        Some(PowerSeries::new(result, precision))
+    }
+}
+
+// The operations that divide coefficients
+impl<T: Field> PowerSeries<T> {
+    // Multiplicative inverse via Newton iteration g <- g * (2 - f * g),
+    // which doubles the number of correct terms each step.
+    // None if the constant term is zero.
+    pub fn inv(&self) -> Option<Self> {
+        let constant = self.coeffs.coeff(0);
+        if constant.is_zero() {
+            return None;
+        }
+        let two = Poly::new(vec![T::one() + T::one()]);
+        let mut inverse = Poly::new(vec![T::one() / constant]);
+        let mut known = 1;
+        while known < self.precision {
+            known = (2 * known).min(self.precision);
+            let error = truncate(self.coeffs.clone(), known) * &inverse;
+            inverse = truncate(inverse * (&two - &truncate(error, known)), known);
+        }
+        Some(PowerSeries::new(inverse, self.precision))
+    }
+
+    // Formal integral with zero constant term; precision grows by one.
+    // Divides by the exponents 1 to precision, so None if the characteristic is one of them.
+    pub fn integral(&self) -> Option<Self> {
+        if !divides_up_to::<T>(self.precision) {
+            return None;
+        }
+        let mut values = vec![T::zero()];
+        let mut power = T::zero();
+        for x in self.coeffs.coeffs() {
+            power = power + T::one();
+            values.push(x.clone() / power.clone());
+        }
+        Some(PowerSeries::new(Poly::new(values), self.precision + 1))
+    }
+
+    // Logarithm as the integral of f' / f, for series with constant term one.
+    // None if the constant term is not one, or the characteristic is below the precision.
+    pub fn log(&self) -> Option<Self> {
+        if !(self.coeffs.coeff(0) - T::one()).is_zero() {
+            return None;
+        }
+        let quotient = self.derivative() * self.inv()?;
+        Some(quotient.integral()?.truncate(self.precision))
+    }
+
+    // Exponential via Newton iteration g <- g * (1 - log g + f), for series with
+    // constant term zero. None if the constant term is not zero, or when log is None.
+    pub fn exp(&self) -> Option<Self> {
+        if !self.coeffs.coeff(0).is_zero() {
+            return None;
+        }
+        let one = Poly::new(vec![T::one()]);
+        let mut exp = one.clone();
+        let mut known = 1;
+        while known < self.precision {
+            known = (2 * known).min(self.precision);
+            let log = PowerSeries::new(exp.clone(), known).log()?.coeffs;
+            let step = one.clone() - log + truncate(self.coeffs.clone(), known);
+            exp = truncate(exp * step, known);
+        }
+        Some(PowerSeries::new(exp, self.precision))
+    }
+
+    // Square root with constant term one via Newton iteration g <- (g + f / g) / 2.
+    // None if the constant term is not one, or the characteristic is 2.
+    pub fn sqrt(&self) -> Option<Self> {
+        if !(self.coeffs.coeff(0) - T::one()).is_zero() || !divides_up_to::<T>(2) {
+            return None;
+        }
+        let two = T::one() + T::one();
+        let mut root = Poly::new(vec![T::one()]);
+        let mut known = 1;
+        while known < self.precision {
+            known = (2 * known).min(self.precision);
+            let inverse = PowerSeries::new(root.clone(), known).inv()?.coeffs;
+            let quotient = truncate(truncate(self.coeffs.clone(), known) * inverse, known);
+            root = (root + quotient).coeff_div(two.clone());
+        }
+        Some(PowerSeries::new(root, self.precision))
+    }
+}
+
+// Whether 1, 2, ..., n are all non-zero in T, so they can be divided by
+fn divides_up_to<T: Ring>(n: usize) -> bool {
+    let characteristic = T::characteristic();
+    characteristic == 0 || characteristic > n as u64
+}
+
+// Drops every term at or above x^precision
+fn truncate<T: Ring>(poly: Poly<T>, precision: usize) -> Poly<T> {
+    let mut values = poly.values;
+    values.truncate(precision);
+    Poly::new(values)
+}
+
+// Polynomial division through the reversed polynomials: rev(q) = rev(a) / rev(b)
+// mod x^(deg a - deg b + 1), where the series inverse takes O(M(n)) instead of
+// the O(n^2) of long division. Expects deg a >= deg b and a non-zero b.
+pub(crate) fn newton_div<T: Field>(dividend: &Poly<T>, divisor: &Poly<T>) -> (Poly<T>, Poly<T>) {
+    let quotient_len = dividend.degree().unwrap() - divisor.degree().unwrap() + 1;
+    let divisor_inv = PowerSeries::new(divisor.reverse(), quotient_len)
+        .inv()
+        .expect("the reversed divisor starts with the leading coefficient");
+    let quotient_rev = PowerSeries::new(dividend.reverse(), quotient_len) * divisor_inv;
+    let mut quotient = quotient_rev.coeffs.values;
+    quotient.resize(quotient_len, T::zero());
+    quotient.reverse();
+    let quotient = Poly::new(quotient);
+    let remainder = dividend.clone() - quotient.clone() * divisor.clone();
+    (quotient, remainder)
+}
+
+impl<T: Ring> std::ops::Add for PowerSeries<T> {
+    type Output = PowerSeries<T>;
+
+    fn add(self, rhs: PowerSeries<T>) -> Self {
+        let precision = self.precision.min(rhs.precision);
+        PowerSeries::new(self.coeffs + rhs.coeffs, precision)
+    }
+}
+
+impl<T: Ring> std::ops::Sub for PowerSeries<T> {
+    type Output = PowerSeries<T>;
+
+    fn sub(self, rhs: PowerSeries<T>) -> Self {
+        let precision = self.precision.min(rhs.precision);
+        PowerSeries::new(self.coeffs - rhs.coeffs, precision)
+    }
+}
+
+impl<T: Ring> std::ops::Mul for PowerSeries<T> {
+    type Output = PowerSeries<T>;
+
+    fn mul(self, rhs: PowerSeries<T>) -> Self {
+        let precision = self.precision.min(rhs.precision);
+        // only the terms below the precision can affect the result
+        let lhs = truncate(self.coeffs, precision);
+        let rhs = truncate(rhs.coeffs, precision);
+        PowerSeries::new(lhs * rhs, precision)
+    }
+}
+
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Display
+    for PowerSeries<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{} + O(x^{})", self.coeffs, self.precision)?;
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Debug
+    for PowerSeries<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_integer_slice, new_from_slice, GF28};
+    use num::rational::Rational64;
+
+    fn rationals(values: &[(i64, i64)]) -> Poly<Rational64> {
+        Poly::new(values.iter().map(|&(n, d)| Rational64::new(n, d)).collect())
+    }
+
+    fn exp_series() -> PowerSeries<Rational64> {
+        let coeffs = rationals(&[(1, 1), (1, 1), (1, 2), (1, 6), (1, 24), (1, 120), (1, 720)]);
+        PowerSeries::new(coeffs, 7)
+    }
+
+    #[test]
+    fn new_truncates() {
+        let t = PowerSeries::new(from_integer_slice(&vec![1, 2, 3, 4]), 2);
+        assert_eq!(t.to_poly(), from_integer_slice(&vec![1, 2]));
+        assert_eq!(t.coeff(1), Some(Rational64::from_integer(2)));
+        assert_eq!(t.coeff(2), None);
+    }
+
+    #[test]
+    fn mul_truncates() {
+        let a = PowerSeries::new(from_integer_slice(&vec![1, 1]), 3);
+        let b = PowerSeries::new(from_integer_slice(&vec![1, 1]), 4);
+        let t = a.clone() * b.clone() * a * b;
+        assert_eq!(t.precision(), 3);
+        assert_eq!(t.to_poly(), from_integer_slice(&vec![1, 4, 6]));
+    }
+
+    #[test]
+    fn inverse_of_one_minus_x() {
+        let t = PowerSeries::new(from_integer_slice(&vec![1, -1]), 6);
+        assert_eq!(
+            t.inv().unwrap().to_poly(),
+            from_integer_slice(&vec![1, 1, 1, 1, 1, 1])
+        );
+    }
+
+    #[test]
+    fn inverse_times_self_is_one() {
+        let t = PowerSeries::new(from_integer_slice(&vec![3, 1, 4, 1, 5, 9, 2, 6]), 8);
+        let inv = t.clone().inv().unwrap();
+        assert_eq!((t * inv).to_poly(), from_integer_slice(&vec![1]));
+    }
+
+    #[test]
+    fn inverse_z2z() {
+        // (1 + x)^-1 = 1 + x + x^2 + ... over Z2
+        let t = PowerSeries::new(new_from_slice(&[0b11]), 9);
+        assert_eq!(t.inv().unwrap().to_poly(), new_from_slice(&[0xff, 1]));
+    }
+
+    #[test]
+    fn no_inverse_without_constant() {
+        let t = PowerSeries::new(from_integer_slice(&vec![0, 1]), 4);
+        assert_eq!(t.inv(), None);
+    }
+
+    #[test]
+    fn log_of_exp() {
+        assert_eq!(
+            exp_series().log().unwrap().to_poly(),
+            from_integer_slice(&vec![0, 1])
+        );
+    }
+
+    #[test]
+    fn exp_of_x() {
+        let x = PowerSeries::new(from_integer_slice(&vec![0, 1]), 7);
+        assert_eq!(x.exp().unwrap(), exp_series());
+    }
+
+    #[test]
+    fn exp_needs_zero_constant() {
+        assert_eq!(exp_series().exp(), None);
+    }
+
+    #[test]
+    fn sqrt_squared() {
+        let t = PowerSeries::new(from_integer_slice(&vec![1, 2, -1, 5, 3]), 5);
+        let root = t.sqrt().unwrap();
+        assert_eq!(root.clone() * root, t);
+    }
+
+    #[test]
+    fn sqrt_of_one_plus_x() {
+        let t = PowerSeries::new(from_integer_slice(&vec![1, 1]), 4);
+        assert_eq!(
+            t.sqrt().unwrap().to_poly(),
+            rationals(&[(1, 1), (1, 2), (-1, 8), (1, 16)])
+        );
+    }
+
+    #[test]
+    fn integral() {
+        let t = PowerSeries::new(from_integer_slice(&vec![1, 2, 3]), 4);
+        let integral = t.integral().unwrap();
+        assert_eq!(integral.to_poly(), from_integer_slice(&vec![0, 1, 1, 1]));
+        assert_eq!(integral.precision(), 5);
+    }
+
+    #[test]
+    fn characteristic_two() {
+        // 1 + x over Z2: the integral of x needs x^2 / 2
+        let t = PowerSeries::new(new_from_slice(&[0b11]), 4);
+        assert_eq!(t.integral(), None);
+        assert_eq!(t.log(), None);
+        assert_eq!(t.sqrt(), None);
+        let x = PowerSeries::new(new_from_slice(&[0b10]), 4);
+        assert_eq!(x.exp(), None);
+        // with precision 1 only the constant term is integrated
+        let one = PowerSeries::new(new_from_slice(&[1]), 1);
+        assert_eq!(one.integral().unwrap().to_poly(), new_from_slice(&[0b10]));
+        let t = PowerSeries::new(Poly::new(vec![GF28::new(1), GF28::new(7)]), 3);
+        assert_eq!(t.sqrt(), None);
+    }
+
+    #[test]
+    fn compose_with_exp() {
+        // exp(2x) from exp(x) and 2x
+        let inner = PowerSeries::new(from_integer_slice(&vec![0, 2]), 7);
+        let composed = exp_series().compose(&inner).unwrap();
+        assert_eq!(composed, inner.exp().unwrap());
+    }
+
+    #[test]
+    fn compose_needs_zero_constant() {
+        let inner = PowerSeries::new(from_integer_slice(&vec![1, 2]), 7);
+        assert_eq!(exp_series().compose(&inner), None);
+    }
+
+    #[test]
+    fn series_display() {
+        let t = PowerSeries::new(from_integer_slice(&vec![1, 2, 3]), 5);
+        assert_eq!(format!("{}", t), "3x^2 + 2x + 1 + O(x^5)");
+    }
+
+    #[test]
+    fn newton_div_matches_long_division() {
+        let a = from_integer_slice(&vec![3, -1, 4, 1, -5, 9, 2, 6, -5, 3]);
+        let b = from_integer_slice(&vec![2, 7, -1, 8]);
+        assert_eq!(newton_div(&a, &b), a.div(&b));
+    }
+
+    #[test]
+    fn newton_div_large_z2z() {
+        // big enough that Poly::div itself picks newton_div
+        let a = new_from_slice(&[0x5a; 600]);
+        let b = new_from_slice(&[0xc3; 300]);
+        let (q, r) = a.div(&b);
+        assert!(r.values.len() < b.values.len());
+        assert_eq!(q * b + r, a);
+    }
+}
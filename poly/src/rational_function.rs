@@ -0,0 +1,550 @@
+use crate::euclidean;
+use crate::gcd;
+use crate::Poly;
+use crate::PowerSeries;
+use crate::{Field, Ring};
+use num::rational::Rational64;
+use num::{BigInt, Integer, One, ToPrimitive, Zero};
+
+// (numerator, factor, power) terms of a partial fraction decomposition
+pub type PartialFractions<T> = Vec<(Poly<T>, Poly<T>, usize)>;
+
+// Struct that represents a quotient of two Polys, always kept in lowest terms
+// with a monic denominator, so equal functions have equal representations
+#[derive(Clone, PartialEq)]
+pub struct RationalFunction<T> {
+    numerator: Poly<T>,
+    denominator: Poly<T>,
+}
+
+impl<T: Field> RationalFunction<T> {
+    // Creates numerator / denominator, reduced to lowest terms
+    pub fn new(numerator: Poly<T>, denominator: Poly<T>) -> Self {
+        if denominator.is_zero() {
+            panic!("Division by zero error");
+        }
+        let common = gcd(&numerator, &denominator);
+        let numerator = numerator.div(&common).0;
+        let denominator = denominator.div(&common).0;
+        let lc = denominator.leading_coeff();
+        RationalFunction {
+            numerator: numerator.coeff_div(lc.clone()),
+            denominator: denominator.coeff_div(lc),
+        }
+    }
+
+    pub fn from_poly(poly: Poly<T>) -> Self {
+        RationalFunction {
+            numerator: poly,
+            denominator: Poly::new(vec![T::one()]),
+        }
+    }
+
+    pub fn numerator(&self) -> &Poly<T> {
+        &self.numerator
+    }
+
+    pub fn denominator(&self) -> &Poly<T> {
+        &self.denominator
+    }
+
+    // The reciprocal, or None for zero
+    pub fn checked_inv(&self) -> Option<Self> {
+        if self.numerator.is_zero() {
+            return None;
+        }
+        Some(RationalFunction::new(
+            self.denominator.clone(),
+            self.numerator.clone(),
+        ))
+    }
+
+    // Evaluates at a given T, or None if value is a pole
+    pub fn evaluate(&self, value: &T) -> Option<T> {
+        let denominator = self.denominator.evaluate(value);
+        if denominator.is_zero() {
+            return None;
+        }
+        Some(self.numerator.evaluate(value) / denominator)
+    }
+
+    // Expands into a power series around 0 (e.g. to read off a generating function),
+    // or None if 0 is a pole
+    pub fn to_power_series(&self, precision: usize) -> Option<PowerSeries<T>> {
+        let inverse = PowerSeries::new(self.denominator.clone(), precision).inv()?;
+        Some(PowerSeries::new(self.numerator.clone(), precision) * inverse)
+    }
+}
+
+impl RationalFunction<Rational64> {
+    // Partial fraction decomposition over Q.
+    // Returns the polynomial part and (numerator, factor, power) terms with
+    //     self = polynomial part + sum of numerator / factor^power
+    // and deg numerator < deg factor. The factors are the monic linear factors from the
+    // rational roots of the denominator, plus the square-free parts left over after
+    // removing them (which have no rational roots, but are not necessarily irreducible).
+    pub fn partial_fractions(&self) -> (Poly<Rational64>, PartialFractions<Rational64>) {
+        let mut factors = Vec::new();
+        for (squarefree, power) in squarefree_factorization(&self.denominator) {
+            let mut rest = squarefree;
+            for root in rational_roots(&rest) {
+                let linear = Poly::from_roots(&[root]);
+                rest = rest.div(&linear).0;
+                factors.push((linear, power));
+            }
+            if rest.degree().is_some_and(|d| d > 0) {
+                factors.push((rest, power));
+            }
+        }
+        decompose(&self.numerator, &self.denominator, factors)
+    }
+}
+
+// Yun's algorithm: splits a monic Poly into pairwise coprime square-free factors,
+// returned with their multiplicities. Needs characteristic 0.
+fn squarefree_factorization<T: Field>(poly: &Poly<T>) -> Vec<(Poly<T>, usize)> {
+    let mut factors = Vec::new();
+    let derivative = poly.derivative();
+    let common = gcd(poly, &derivative);
+    let mut rest = poly.div(&common).0;
+    let mut remaining_derivative = derivative.div(&common).0 - rest.derivative();
+    let mut power = 1;
+    while rest.degree().is_some_and(|d| d > 0) {
+        let factor = gcd(&rest, &remaining_derivative);
+        rest = rest.div(&factor).0;
+        remaining_derivative = remaining_derivative.div(&factor).0 - rest.derivative();
+        if factor.degree().is_some_and(|d| d > 0) {
+            factors.push((factor, power));
+        }
+        power += 1;
+    }
+    factors
+}
+
+// The numerator and denominator of a rational root have to divide the constant and
+// leading coefficients, found by trial division up to the square root, so roots of
+// Polys with bigger ones than this are left to the square-free parts
+const MAX_TRIAL_DIVISION: u64 = 1 << 40;
+
+// Finds the rational roots of a Poly with the rational root theorem. Only finds 0
+// if the constant or leading coefficient is past MAX_TRIAL_DIVISION once scaled.
+fn rational_roots(poly: &Poly<Rational64>) -> Vec<Rational64> {
+    // scale to integer coefficients, in BigInt since the lcm of the denominators
+    // easily passes i64
+    let lcm = poly
+        .coeffs()
+        .fold(BigInt::one(), |lcm, x| lcm.lcm(&BigInt::from(*x.denom())));
+    let ints: Vec<BigInt> = poly
+        .coeffs()
+        .map(|x| &lcm / x.denom() * x.numer())
+        .collect();
+    let mut roots = Vec::new();
+    // strip the factors of x, which give the root 0
+    let lowest = ints.iter().position(|x| !x.is_zero()).unwrap_or(0);
+    if lowest > 0 {
+        roots.push(Rational64::from_integer(0));
+    }
+    let ints = &ints[lowest..];
+    if ints.len() < 2 || ints[0].is_zero() {
+        return roots;
+    }
+    let (numerators, denominators) = match (divisors(&ints[0]), divisors(&ints[ints.len() - 1])) {
+        (Some(numerators), Some(denominators)) => (numerators, denominators),
+        _ => return roots,
+    };
+    for p in numerators.iter() {
+        for q in denominators.iter() {
+            for p in [*p, -p] {
+                let candidate = Rational64::new(p, *q);
+                if !roots.contains(&candidate) && is_root(ints, p, *q) {
+                    roots.push(candidate);
+                }
+            }
+        }
+    }
+    roots
+}
+
+// Whether p / q is a root, from q^n P(p / q) = sum of a_k p^k q^(n-k) worked out exactly
+fn is_root(ints: &[BigInt], p: i64, q: i64) -> bool {
+    let (p, q) = (BigInt::from(p), BigInt::from(q));
+    let mut q_power = BigInt::one();
+    let mut sum = BigInt::zero();
+    for a in ints.iter().rev() {
+        sum = sum * &p + a * &q_power;
+        q_power *= &q;
+    }
+    sum.is_zero()
+}
+
+// Positive divisors of a non-zero integer, or None past MAX_TRIAL_DIVISION
+fn divisors(n: &BigInt) -> Option<Vec<i64>> {
+    let n = n
+        .magnitude()
+        .to_u64()
+        .filter(|n| *n <= MAX_TRIAL_DIVISION)?;
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    let mut i = 1;
+    while i <= n / i {
+        if n % i == 0 {
+            small.push(i as i64);
+            if i != n / i {
+                large.push((n / i) as i64);
+            }
+        }
+        i += 1;
+    }
+    small.extend(large.into_iter().rev());
+    Some(small)
+}
+
+// Splits numerator / denominator into partial fractions, given the pairwise coprime
+// factors of the denominator with their multiplicities
+fn decompose<T: Field>(
+    numerator: &Poly<T>,
+    denominator: &Poly<T>,
+    factors: Vec<(Poly<T>, usize)>,
+) -> (Poly<T>, PartialFractions<T>) {
+    let (polynomial, remainder) = numerator.div(denominator);
+    let mut terms = Vec::new();
+    for (factor, power) in factors {
+        let mut prime_power = Poly::new(vec![T::one()]);
+        for _i in 0..power {
+            prime_power *= &factor;
+        }
+        let cofactor = denominator.div(&prime_power).0;
+        // remainder / denominator = sum of a_i / prime_power_i with a_i = remainder / cofactor_i
+        let (inverse, _t, _gcd) = euclidean(&cofactor, &prime_power);
+        let mut part = (remainder.clone() * inverse).modulus(&prime_power);
+        // write part in base factor: part / factor^power = sum of c_k / factor^(power - k)
+        let mut expansion = Vec::new();
+        for k in 0..power {
+            let (quotient, digit) = part.div(&factor);
+            if !digit.is_zero() {
+                expansion.push((digit, factor.clone(), power - k));
+            }
+            part = quotient;
+        }
+        expansion.reverse();
+        terms.extend(expansion);
+    }
+    (polynomial, terms)
+}
+
+impl<T: Field> std::ops::Add for RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn add(self, rhs: RationalFunction<T>) -> Self {
+        RationalFunction::new(
+            self.numerator * rhs.denominator.clone() + rhs.numerator * self.denominator.clone(),
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl<T: Field> std::ops::Sub for RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn sub(self, rhs: RationalFunction<T>) -> Self {
+        RationalFunction::new(
+            self.numerator * rhs.denominator.clone() - rhs.numerator * self.denominator.clone(),
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl<T: Field> std::ops::Mul for RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn mul(self, rhs: RationalFunction<T>) -> Self {
+        RationalFunction::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl<T: Field> std::ops::Div for RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn div(self, rhs: RationalFunction<T>) -> Self {
+        RationalFunction::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+    }
+}
+
+impl<T: Field> num::CheckedDiv for RationalFunction<T> {
+    fn checked_div(&self, rhs: &RationalFunction<T>) -> Option<RationalFunction<T>> {
+        Some(self.clone() * rhs.checked_inv()?)
+    }
+}
+
+impl<T: Field> num::traits::Inv for RationalFunction<T> {
+    type Output = RationalFunction<T>;
+
+    fn inv(self) -> Self {
+        RationalFunction::new(self.denominator, self.numerator)
+    }
+}
+
+impl<T: Field> Ring for RationalFunction<T> {
+    fn characteristic() -> u64 {
+        T::characteristic()
+    }
+}
+
+impl<T: Field> Field for RationalFunction<T> {
+    fn checked_inv(&self) -> Option<Self> {
+        RationalFunction::checked_inv(self)
+    }
+}
+
+impl<T: Field> num::Zero for RationalFunction<T> {
+    fn zero() -> Self {
+        RationalFunction::from_poly(Poly::new(vec![T::zero()]))
+    }
+    fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+}
+
+impl<T: Field> num::One for RationalFunction<T> {
+    fn one() -> Self {
+        RationalFunction::from_poly(Poly::new(vec![T::one()]))
+    }
+    fn is_one(&self) -> bool {
+        // both sides are monic in lowest terms, so this is only true for 1 / 1
+        self.numerator == self.denominator
+    }
+}
+
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Display
+    for RationalFunction<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        // the denominator is monic, so a constant one is 1
+        if self.denominator.values.len() == 1 {
+            write!(f, "{}", self.numerator)?;
+        } else {
+            write!(f, "({}) / ({})", self.numerator, self.denominator)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Debug
+    for RationalFunction<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_integer_slice, interpolate, new_from_slice};
+    use num::{One, Zero};
+
+    fn rf(numerator: &[i64], denominator: &[i64]) -> RationalFunction<Rational64> {
+        RationalFunction::new(
+            from_integer_slice(&numerator.to_vec()),
+            from_integer_slice(&denominator.to_vec()),
+        )
+    }
+
+    fn rationals(values: &[(i64, i64)]) -> Poly<Rational64> {
+        Poly::new(values.iter().map(|&(n, d)| Rational64::new(n, d)).collect())
+    }
+
+    #[test]
+    fn new_reduces() {
+        // (x^2 - 1) / (2x + 2) = (x - 1) / 2
+        let t = rf(&[-1, 0, 1], &[2, 2]);
+        assert_eq!(t.numerator(), &rationals(&[(-1, 2), (1, 2)]));
+        assert_eq!(t.denominator(), &from_integer_slice(&vec![1]));
+    }
+
+    #[test]
+    fn new_makes_denominator_monic() {
+        let t = rf(&[1], &[1, 3]);
+        assert_eq!(t.numerator(), &rationals(&[(1, 3)]));
+        assert_eq!(t.denominator(), &rationals(&[(1, 3), (1, 1)]));
+    }
+
+    #[test]
+    fn reduces_with_zero_constant_terms() {
+        assert_eq!(rf(&[0, 0, 3], &[0, 0, 0, 6]), rf(&[1], &[0, 2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero error")]
+    fn zero_denominator_panics() {
+        let _ = rf(&[1, 2], &[0]);
+    }
+
+    #[test]
+    fn checked_inv_and_div() {
+        use num::{traits::Inv, CheckedDiv};
+        let t = rf(&[1, 2], &[0, 1]);
+        assert_eq!(t.checked_inv(), Some(rf(&[0, 1], &[1, 2])));
+        assert_eq!(t.clone().inv(), rf(&[0, 1], &[1, 2]));
+        assert_eq!(RationalFunction::<Rational64>::zero().checked_inv(), None);
+        assert_eq!(t.checked_div(&RationalFunction::zero()), None);
+        assert_eq!(t.checked_div(&t), Some(RationalFunction::one()));
+    }
+
+    #[test]
+    fn add_and_sub() {
+        // 1 / x + 1 / (x + 1) = (2x + 1) / (x^2 + x)
+        let a = rf(&[1], &[0, 1]);
+        let b = rf(&[1], &[1, 1]);
+        assert_eq!(a.clone() + b.clone(), rf(&[1, 2], &[0, 1, 1]));
+        assert_eq!(a.clone() + b.clone() - b, a);
+    }
+
+    #[test]
+    fn mul_and_div_cancel() {
+        let a = rf(&[1, 2, 3], &[4, 0, 1]);
+        let b = rf(&[-1, 1], &[1, 1, 1]);
+        assert_eq!(a.clone() * b.clone() / b, a);
+        assert!((a.clone() / a).is_one());
+    }
+
+    #[test]
+    fn zero_and_one() {
+        assert!(RationalFunction::<Rational64>::zero().is_zero());
+        assert!(RationalFunction::<Rational64>::one().is_one());
+        assert!(!rf(&[1, 1], &[2, 1]).is_one());
+        assert!(rf(&[0], &[2, 1]).is_zero());
+    }
+
+    #[test]
+    fn evaluate_and_poles() {
+        let t = rf(&[1, 1], &[-4, 0, 1]);
+        assert_eq!(
+            t.evaluate(&Rational64::from_integer(3)),
+            Some(Rational64::new(4, 5))
+        );
+        assert_eq!(t.evaluate(&Rational64::from_integer(2)), None);
+        // the x + 1 cancels, so -1 is not a pole of (x + 1) / (x^2 - 1)
+        let t = rf(&[1, 1], &[-1, 0, 1]);
+        assert_eq!(
+            t.evaluate(&Rational64::from_integer(-1)),
+            Some(Rational64::new(-1, 2))
+        );
+    }
+
+    #[test]
+    fn fibonacci_generating_function() {
+        // x / (1 - x - x^2)
+        let t = rf(&[0, 1], &[1, -1, -1]);
+        assert_eq!(
+            t.to_power_series(8).unwrap().to_poly(),
+            from_integer_slice(&vec![0, 1, 1, 2, 3, 5, 8, 13])
+        );
+    }
+
+    #[test]
+    fn partial_fractions_repeated_root() {
+        // (x + 1) / ((x - 1)^2 (x + 2)) = 1/9 / (x - 1) + 2/3 / (x - 1)^2 - 1/9 / (x + 2)
+        let t = rf(&[1, 1], &[2, -3, 0, 1]);
+        let (polynomial, terms) = t.partial_fractions();
+        assert!(polynomial.is_zero());
+        assert_eq!(terms.len(), 3);
+        assert!(terms.contains(&(rationals(&[(1, 9)]), from_integer_slice(&vec![-1, 1]), 1)));
+        assert!(terms.contains(&(rationals(&[(2, 3)]), from_integer_slice(&vec![-1, 1]), 2)));
+        assert!(terms.contains(&(rationals(&[(-1, 9)]), from_integer_slice(&vec![2, 1]), 1)));
+    }
+
+    #[test]
+    fn rational_roots_with_big_coefficients() {
+        // x^2 + i64::MIN x has the root 2^63, which no Rational64 holds
+        let t = from_integer_slice(&vec![0, i64::MIN, 1]);
+        assert_eq!(rational_roots(&t), vec![Rational64::zero()]);
+        let t = from_integer_slice(&vec![i64::MIN, 1]);
+        assert!(rational_roots(&t).is_empty());
+        // (x - 1)(x - i64::MAX) is past trial division, (x - 1)(x - 2^40) isn't
+        let t = from_integer_slice(&vec![i64::MAX, i64::MIN, 1]);
+        assert!(rational_roots(&t).is_empty());
+        let t = Poly::from_roots(&[Rational64::one(), Rational64::from_integer(1 << 40)]);
+        assert_eq!(
+            rational_roots(&t),
+            vec![Rational64::one(), Rational64::from_integer(1 << 40)]
+        );
+    }
+
+    #[test]
+    fn rational_roots_with_big_denominators() {
+        // (x - 1)(x^2 / 2^40 + 1 / 3^25), where the lcm 2^40 3^25 of the denominators
+        // is past i64
+        let t = rationals(&[
+            (-1, 3i64.pow(25)),
+            (1, 3i64.pow(25)),
+            (-1, 1 << 40),
+            (1, 1 << 40),
+        ]);
+        assert_eq!(rational_roots(&t), vec![Rational64::one()]);
+        let t = rationals(&[(-1, 3i64.pow(39)), (0, 1), (1, 1 << 62)]);
+        assert!(rational_roots(&t).is_empty());
+    }
+
+    #[test]
+    fn partial_fractions_irreducible_quadratic() {
+        // x^3 / (x^2 + 1) = x - x / (x^2 + 1)
+        let t = rf(&[0, 0, 0, 1], &[1, 0, 1]);
+        let (polynomial, terms) = t.partial_fractions();
+        assert_eq!(polynomial, from_integer_slice(&vec![0, 1]));
+        assert_eq!(
+            terms,
+            vec![(
+                from_integer_slice(&vec![0, -1]),
+                from_integer_slice(&vec![1, 0, 1]),
+                1
+            )]
+        );
+    }
+
+    #[test]
+    fn partial_fractions_sum_back() {
+        // (3x^5 + 1) / (x (2x - 1)^2 (x^2 + x + 1))
+        let denominator = from_integer_slice(&vec![0, 1])
+            * from_integer_slice(&vec![-1, 2])
+            * from_integer_slice(&vec![-1, 2])
+            * from_integer_slice(&vec![1, 1, 1]);
+        let t = RationalFunction::new(from_integer_slice(&vec![1, 0, 0, 0, 0, 3]), denominator);
+        let (polynomial, terms) = t.partial_fractions();
+        assert_eq!(polynomial.degree(), Some(0));
+        assert_eq!(terms.len(), 4);
+        let mut sum = RationalFunction::from_poly(polynomial);
+        for (numerator, factor, power) in terms {
+            let mut denominator = from_integer_slice(&vec![1]);
+            for _i in 0..power {
+                denominator *= &factor;
+            }
+            sum = sum + RationalFunction::new(numerator, denominator);
+        }
+        assert_eq!(sum, t);
+    }
+
+    #[test]
+    fn as_poly_coefficients() {
+        // interpolate through (1, 1 / x) and (2, 1 / (x + 1)) with rational function values
+        let xs = vec![rf(&[1], &[1]), rf(&[2], &[1])];
+        let ys = vec![rf(&[1], &[0, 1]), rf(&[1], &[1, 1])];
+        let t = interpolate(&xs, &ys).unwrap();
+        assert_eq!(t.evaluate(&xs[0]), ys[0]);
+        assert_eq!(t.evaluate(&xs[1]), ys[1]);
+    }
+
+    #[test]
+    fn over_z2z() {
+        // (x^2 + 1) / (x + 1) = x + 1 over Z2
+        let t = RationalFunction::new(new_from_slice(&[0b101]), new_from_slice(&[0b11]));
+        assert_eq!(t, RationalFunction::from_poly(new_from_slice(&[0b11])));
+    }
+}
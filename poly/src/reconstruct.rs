@@ -0,0 +1,135 @@
+use crate::euclidean::is_coprime;
+use crate::partial_euclidean;
+use crate::Field;
+use crate::Poly;
+use num::One;
+
+// Finds n / d with n = d * f (mod m), deg n <= deg_num and deg d <= deg_den.
+// The fraction comes back in lowest terms with a monic denominator, or None if
+// no such fraction with d invertible mod m exists.
+// The answer is unique when deg_num + deg_den < deg m.
+pub fn rational_reconstruct<T: Field>(
+    f: &Poly<T>,
+    m: &Poly<T>,
+    deg_num: usize,
+    deg_den: usize,
+) -> Option<(Poly<T>, Poly<T>)> {
+    let f = f.modulus(m);
+    if m.degree().is_some_and(|d| d <= deg_num) {
+        return Some((f, Poly::one()));
+    }
+    let (_s, t, r) = partial_euclidean(m, &f, deg_num);
+    if t.degree().is_none_or(|d| d > deg_den) || !is_coprime(&t, m) {
+        return None;
+    }
+    // t and m are coprime, so r / t is already in lowest terms
+    let lc = t.leading_coeff();
+    Some((
+        Poly::normalize_from_value(r, &lc),
+        Poly::normalize_from_value(t, &lc),
+    ))
+}
+
+// Returns the [deg_num / deg_den] Padé approximant n / d of a power series,
+// given by its first deg_num + deg_den + 1 coefficients (more are ignored).
+// n / d agrees with the series up to x^(deg_num + deg_den) and d(0) = 1.
+pub fn pade<T: Field>(
+    series: &Poly<T>,
+    deg_num: usize,
+    deg_den: usize,
+) -> Option<(Poly<T>, Poly<T>)> {
+    let mut modulus = vec![T::zero(); deg_num + deg_den + 1];
+    modulus.push(T::one());
+    let (num, den) = rational_reconstruct(series, &Poly::new(modulus), deg_num, deg_den)?;
+    // coprime with x^k means the constant term is not zero
+    let constant = den.coeff(0);
+    Some((
+        Poly::normalize_from_value(num, &constant),
+        Poly::normalize_from_value(den, &constant),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::from_integer_slice;
+    use num::rational::Rational64;
+
+    fn rationals(values: &[(i64, i64)]) -> Poly<Rational64> {
+        Poly::new(values.iter().map(|&(n, d)| Rational64::new(n, d)).collect())
+    }
+
+    #[test]
+    fn reconstruct_known_fraction() {
+        // n = 2x + 1, d = 3x^2 - x + 1
+        let num = from_integer_slice(&vec![1, 2]);
+        let den = from_integer_slice(&vec![1, -1, 3]);
+        let m = from_integer_slice(&vec![-3, 0, 0, 0, 0, 1]);
+        let f = (num * den.inv_mod(&m)).modulus(&m);
+        assert_eq!(
+            rational_reconstruct(&f, &m, 1, 2),
+            Some((
+                rationals(&[(1, 3), (2, 3)]),
+                rationals(&[(1, 3), (-1, 3), (1, 1)])
+            ))
+        );
+    }
+
+    #[test]
+    fn reconstruct_small_modulus() {
+        let f = from_integer_slice(&vec![1, 2, 3]);
+        let m = from_integer_slice(&vec![0, 0, 1]);
+        assert_eq!(
+            rational_reconstruct(&f, &m, 2, 0),
+            Some((
+                from_integer_slice(&vec![1, 2]),
+                from_integer_slice(&vec![1])
+            ))
+        );
+    }
+
+    #[test]
+    fn reconstruct_denominator_too_big() {
+        let num = from_integer_slice(&vec![1, 2]);
+        let den = from_integer_slice(&vec![1, -1, 3]);
+        let m = from_integer_slice(&vec![-3, 0, 0, 0, 0, 1]);
+        let f = (num * den.inv_mod(&m)).modulus(&m);
+        assert_eq!(rational_reconstruct(&f, &m, 1, 1), None);
+    }
+
+    #[test]
+    fn pade_of_exp() {
+        let exp = rationals(&[(1, 1), (1, 1), (1, 2), (1, 6), (1, 24)]);
+        assert_eq!(
+            pade(&exp, 1, 1),
+            Some((rationals(&[(1, 1), (1, 2)]), rationals(&[(1, 1), (-1, 2)])))
+        );
+        assert_eq!(
+            pade(&exp, 2, 2),
+            Some((
+                rationals(&[(1, 1), (1, 2), (1, 12)]),
+                rationals(&[(1, 1), (-1, 2), (1, 12)])
+            ))
+        );
+    }
+
+    #[test]
+    fn pade_of_rational_series_is_exact() {
+        // 1 / (1 - x)^2 = 1 + 2x + 3x^2 + ...
+        let series = from_integer_slice(&vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            pade(&series, 0, 2),
+            Some((
+                from_integer_slice(&vec![1]),
+                from_integer_slice(&vec![1, -2, 1])
+            ))
+        );
+    }
+
+    #[test]
+    fn pade_without_normal_form() {
+        // any [1/1] solution for 1 + x^2 has d(0) = 0
+        let series = from_integer_slice(&vec![1, 0, 1]);
+        assert_eq!(pade(&series, 1, 1), None);
+    }
+}
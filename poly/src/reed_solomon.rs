@@ -0,0 +1,331 @@
+use crate::algebra::pow;
+use crate::berlekamp_massey;
+use crate::lagrange::evaluate_many;
+use crate::Error;
+use crate::FiniteField;
+use crate::Poly;
+use std::collections::HashSet;
+
+// A Reed-Solomon code of length n with k message symbols, in the generator Poly view
+// that QR codes use: the codewords are the multiples of
+// g = (x - a^b)(x - a^(b+1))...(x - a^(b+n-k-1)), with b the first root.
+// Codewords are written highest power first, so position i stands for x^(n-1-i) and is
+// told apart by its locator a^(n-1-i). A systematic codeword is the message followed by
+// n - k check symbols, and e errors with f erasures are corrected when 2e + f <= n - k.
+#[derive(Clone, PartialEq)]
+pub struct ReedSolomon<T> {
+    n: usize,
+    k: usize,
+    first_root: usize,
+    generator: Poly<T>,
+    // the roots of g, where the syndromes are taken
+    roots: Vec<T>,
+    // the locator of each position and its inverse
+    locators: Vec<T>,
+    inverse_locators: Vec<T>,
+}
+
+impl<T: FiniteField> ReedSolomon<T> {
+    // Errors unless 0 < k < n and the locators a^0 to a^(n-1) are distinct and non-zero
+    pub fn new(n: usize, k: usize, alpha: T, first_root: usize) -> Result<Self, Error> {
+        if k == 0 || k >= n || alpha.is_zero() {
+            return Err(Error::InvalidCode { n, k });
+        }
+        let mut seen = HashSet::new();
+        let mut locators = Vec::with_capacity(n);
+        let mut locator = T::one();
+        for _ in 0..n {
+            if !seen.insert(locator.clone()) {
+                return Err(Error::InvalidCode { n, k });
+            }
+            locators.push(locator.clone());
+            locator = locator * alpha.clone();
+        }
+        // position i has locator a^(n-1-i)
+        locators.reverse();
+        let inverse_locators = locators.iter().map(|locator| locator.inv()).collect();
+        let roots: Vec<T> = (0..n - k).map(|j| pow(&alpha, first_root + j)).collect();
+        Ok(ReedSolomon {
+            n,
+            k,
+            generator: Poly::from_roots(&roots),
+            first_root,
+            roots,
+            locators,
+            inverse_locators,
+        })
+    }
+
+    // The codeword length
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    // The message length
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn generator(&self) -> &Poly<T> {
+        &self.generator
+    }
+
+    // The message followed by the negated remainder of message * x^(n-k) mod g
+    pub fn encode(&self, message: &[T]) -> Result<Vec<T>, Error> {
+        check_len(message, self.k)?;
+        let checks = self.n - self.k;
+        let shifted = word_to_poly(message) * Poly::monomial(T::one(), checks);
+        let remainder = shifted.modulus(&self.generator);
+        let mut codeword = message.to_vec();
+        codeword.extend((0..checks).rev().map(|i| T::zero() - remainder.coeff(i)));
+        Ok(codeword)
+    }
+
+    // The received word evaluated at the roots of g, which are all zero
+    // exactly when it is a codeword
+    pub fn syndromes(&self, received: &[T]) -> Result<Vec<T>, Error> {
+        check_len(received, self.n)?;
+        Ok(evaluate_many(&word_to_poly(received), &self.roots))
+    }
+
+    // The nearest codeword, given the positions known to be unreliable
+    pub fn correct(&self, received: &[T], erasures: &[usize]) -> Result<Vec<T>, Error> {
+        let syndromes = self.syndromes(received)?;
+        let mut erasures = erasures.to_vec();
+        erasures.sort();
+        erasures.dedup();
+        if let Some(position) = erasures.last().filter(|position| **position >= self.n) {
+            return Err(Error::InvalidErasure {
+                position: *position,
+                n: self.n,
+            });
+        }
+        if syndromes.iter().all(|syndrome| syndrome.is_zero()) {
+            return Ok(received.to_vec());
+        }
+        let checks = self.n - self.k;
+        if erasures.len() > checks {
+            return Err(Error::TooManyErrors);
+        }
+        let syndrome = Poly::new(syndromes);
+        // the erasure locator (1 - X x)... over the erased locators X
+        let erasure_locator: Poly<T> = erasures
+            .iter()
+            .map(|position| Poly::new(vec![T::one(), T::zero() - self.locators[*position].clone()]))
+            .product();
+        // past the first f terms, the Forney syndromes S * erasure_locator no longer see
+        // the erasures, so they're generated by the error locator alone
+        let forney = truncate(&(&syndrome * &erasure_locator), checks);
+        let forney: Vec<T> = (erasures.len()..checks).map(|j| forney.coeff(j)).collect();
+        let (error_locator, errors) = berlekamp_massey(&forney);
+        if 2 * errors + erasures.len() > checks || error_locator.degree() != Some(errors) {
+            return Err(Error::TooManyErrors);
+        }
+        let locator = &error_locator * &erasure_locator;
+        let evaluator = truncate(&(&syndrome * &locator), checks);
+        let derivative = locator.derivative();
+        // Chien search: the positions are where the locator vanishes at 1 / X
+        let positions: Vec<usize> = evaluate_many(&locator, &self.inverse_locators)
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_zero())
+            .map(|(position, _)| position)
+            .collect();
+        if Some(positions.len()) != locator.degree() {
+            return Err(Error::TooManyErrors);
+        }
+        // Forney: the error at X is -X^(1-b) evaluator(1/X) / locator'(1/X)
+        let mut corrected = received.to_vec();
+        for position in positions {
+            let x = self.locators[position].clone();
+            let x_inv = self.inverse_locators[position].clone();
+            let slope = derivative.evaluate(&x_inv);
+            if slope.is_zero() {
+                return Err(Error::TooManyErrors);
+            }
+            let error = x * pow(&x_inv, self.first_root) * evaluator.evaluate(&x_inv) / slope;
+            corrected[position] = corrected[position].clone() + error;
+        }
+        // more errors than the code corrects can still leave a locator that looks right
+        if !self
+            .syndromes(&corrected)?
+            .iter()
+            .all(|syndrome| syndrome.is_zero())
+        {
+            return Err(Error::TooManyErrors);
+        }
+        Ok(corrected)
+    }
+
+    // The message of the nearest codeword
+    pub fn decode(&self, received: &[T], erasures: &[usize]) -> Result<Vec<T>, Error> {
+        let mut codeword = self.correct(received, erasures)?;
+        codeword.truncate(self.k);
+        Ok(codeword)
+    }
+}
+
+fn check_len<T>(word: &[T], expected: usize) -> Result<(), Error> {
+    if word.len() != expected {
+        return Err(Error::WrongLength {
+            len: word.len(),
+            expected,
+        });
+    }
+    Ok(())
+}
+
+// The first symbol is the highest power
+fn word_to_poly<T: FiniteField>(word: &[T]) -> Poly<T> {
+    Poly::new(word.iter().rev().cloned().collect())
+}
+
+// poly mod x^len
+fn truncate<T: FiniteField>(poly: &Poly<T>, len: usize) -> Poly<T> {
+    Poly::new((0..len).map(|i| poly.coeff(i)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GF2256, GF28};
+    use num::Zero;
+    use proptest::prelude::*;
+
+    fn bytes(values: &[u8]) -> Vec<GF28> {
+        values.iter().map(|byte| GF28::new(*byte)).collect()
+    }
+
+    fn qr(n: usize, k: usize) -> ReedSolomon<GF28> {
+        ReedSolomon::new(n, k, GF28::primitive_element(), 0).unwrap()
+    }
+
+    #[test]
+    fn qr_code_check_symbols() {
+        // the data and error correction codewords of "HELLO WORLD" in a version 1-M QR code
+        let message = bytes(&[
+            32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+        ]);
+        let codeword = qr(26, 16).encode(&message).unwrap();
+        assert_eq!(codeword[..16], message);
+        assert_eq!(
+            codeword[16..],
+            bytes(&[196, 35, 39, 119, 235, 215, 231, 226, 93, 23])
+        );
+        assert!(qr(26, 16)
+            .syndromes(&codeword)
+            .unwrap()
+            .iter()
+            .all(|syndrome| syndrome.is_zero()));
+    }
+
+    // e errors and f erasures with 2e + f <= 16, at distinct positions of a length 40 word
+    fn errors_and_erasures() -> impl Strategy<Value = (Vec<usize>, Vec<usize>)> {
+        let positions = proptest::sample::subsequence((0..40).collect::<Vec<_>>(), 16);
+        (positions.prop_shuffle(), 0..=8usize, 0..=16usize).prop_map(
+            |(positions, errors, erasures)| {
+                let erasures = erasures.min(16 - 2 * errors);
+                (
+                    positions[..errors].to_vec(),
+                    positions[errors..errors + erasures].to_vec(),
+                )
+            },
+        )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn corrects_errors_and_erasures(
+            message in proptest::collection::vec(any::<u8>(), 24),
+            (errors, erased) in errors_and_erasures(),
+            flips in proptest::collection::vec(1..=255u8, 16),
+        ) {
+            let code = ReedSolomon::new(40, 24, GF28::primitive_element(), 1).unwrap();
+            let message = bytes(&message);
+            let codeword = code.encode(&message).unwrap();
+            let mut received = codeword.clone();
+            for (position, flip) in errors.iter().chain(erased.iter()).zip(flips) {
+                received[*position] = received[*position] + GF28::new(flip);
+            }
+            prop_assert_eq!(code.correct(&received, &erased).unwrap(), codeword);
+            prop_assert_eq!(code.decode(&received, &erased).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn erasures_can_be_correct_symbols() {
+        let code = qr(15, 9);
+        let codeword = code.encode(&bytes(&[1, 2, 3, 4, 5, 6, 7, 8, 9])).unwrap();
+        assert_eq!(code.correct(&codeword, &[0, 14]).unwrap(), codeword);
+        let mut received = codeword.clone();
+        received[3] = GF28::new(0);
+        assert_eq!(code.correct(&received, &[3, 3, 8]).unwrap(), codeword);
+    }
+
+    #[test]
+    fn too_many_errors() {
+        let code = qr(15, 9);
+        let codeword = code.encode(&bytes(&[9; 9])).unwrap();
+        let mut received = codeword.clone();
+        for symbol in received.iter_mut().take(4) {
+            *symbol = *symbol + GF28::new(1);
+        }
+        // four errors can only decode to some other codeword, never this one
+        assert_ne!(code.correct(&received, &[]).ok(), Some(codeword));
+        assert_eq!(
+            code.correct(&received, &[0, 1, 2, 3, 4, 5, 6]),
+            Err(Error::TooManyErrors)
+        );
+    }
+
+    #[test]
+    fn over_gf2256() {
+        let alpha = GF2256::primitive_element();
+        let code = ReedSolomon::new(10, 4, alpha, 0).unwrap();
+        let message: Vec<GF2256> = ["1234", "deadbeef", "0", "abcdef0123456789"]
+            .iter()
+            .map(|hex| GF2256::from_hex(hex).unwrap())
+            .collect();
+        let codeword = code.encode(&message).unwrap();
+        let mut received = codeword.clone();
+        received[1] = GF2256::from_hex("abc").unwrap();
+        received[6] = GF2256::from_hex("0").unwrap();
+        received[9] = GF2256::from_hex("1").unwrap();
+        assert_eq!(code.decode(&received, &[9]).unwrap(), message);
+    }
+
+    #[test]
+    fn invalid_codes_and_lengths() {
+        let alpha = GF28::primitive_element();
+        assert!(ReedSolomon::new(255, 223, alpha, 0).is_ok());
+        // x only has 255 distinct powers
+        assert_eq!(
+            ReedSolomon::new(256, 200, alpha, 0).err(),
+            Some(Error::InvalidCode { n: 256, k: 200 })
+        );
+        assert!(ReedSolomon::new(10, 10, alpha, 0).is_err());
+        assert!(ReedSolomon::new(10, 0, alpha, 0).is_err());
+        assert!(ReedSolomon::new(10, 5, GF28::new(1), 0).is_err());
+        assert_eq!(
+            qr(10, 5).encode(&bytes(&[1, 2])),
+            Err(Error::WrongLength {
+                len: 2,
+                expected: 5
+            })
+        );
+        assert!(qr(10, 5).decode(&bytes(&[0; 9]), &[]).is_err());
+    }
+
+    #[test]
+    fn erasure_past_the_end() {
+        assert_eq!(
+            qr(10, 5).correct(&bytes(&[0; 10]), &[3, 10]),
+            Err(Error::InvalidErasure {
+                position: 10,
+                n: 10
+            })
+        );
+    }
+}
@@ -1,14 +1,16 @@
 use crate::gf_2_256;
 use crate::interpolate;
+use crate::lagrange::evaluate_many;
 use crate::new_from_slice;
+use crate::Error;
+use crate::FieldElement;
 use crate::Poly;
 use base64::prelude::*;
 pub use gf_2_256::GF2256;
+use rand_core::{OsRng, RngCore};
 use serde::Deserialize;
 use serde::Serialize;
 
-use getrandom;
-
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Share {
     ShamirShare { x: GF2256, y: GF2256 },
@@ -22,20 +24,20 @@ impl Share {
     pub fn get_x_shamir(&self) -> Result<GF2256, Error> {
         match self {
             Share::ShamirShare { x, y: _ } => Ok(x.clone()),
-            Share::XorShare(_) => Err(Error),
+            Share::XorShare(_) => Err(Error::WrongShareVariant),
         }
     }
 
     pub fn get_y_shamir(&self) -> Result<GF2256, Error> {
         match self {
             Share::ShamirShare { x: _, y } => Ok(y.clone()),
-            Share::XorShare(_) => Err(Error),
+            Share::XorShare(_) => Err(Error::WrongShareVariant),
         }
     }
 
     pub fn len_xor(&self) -> Result<usize, Error> {
         match self {
-            Share::ShamirShare { x: _, y: _ } => Err(Error),
+            Share::ShamirShare { x: _, y: _ } => Err(Error::WrongShareVariant),
             Share::XorShare(a) => Ok(a.len()),
         }
     }
@@ -49,28 +51,6 @@ impl Share {
         }
     }
 }
-#[derive(Clone, Debug)]
-pub struct Error;
-
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "noooooooo")?;
-        Ok(())
-    }
-}
-
-impl From<getrandom::Error> for Error {
-    fn from(_err: getrandom::Error) -> Self {
-        Self
-    }
-}
-
-impl From<std::fmt::Error> for Error {
-    fn from(_err: std::fmt::Error) -> Self {
-        Self
-    }
-}
-
 pub trait SecretSharer {
     fn encode(&self, secret: &[u8]) -> Result<Vec<Share>, Error>;
     fn decode(&self, shares: &Vec<Share>) -> Result<Vec<u8>, Error>;
@@ -87,29 +67,35 @@ impl ShamirSharer {
             threshold,
         }
     }
-}
 
-impl SecretSharer for ShamirSharer {
-    fn encode(&self, secret: &[u8]) -> Result<Vec<Share>, Error> {
+    // encode, drawing the random coefficients from rng
+    pub fn encode_with_rng(
+        &self,
+        secret: &[u8],
+        rng: &mut impl RngCore,
+    ) -> Result<Vec<Share>, Error> {
         if secret.len() > 32 {
-            return Err(Error);
+            return Err(Error::SecretTooLong {
+                len: secret.len(),
+                max: 32,
+            });
+        }
+        if self.threshold == 0 || self.threshold as usize > self.share_count {
+            return Err(Error::InvalidThreshold {
+                threshold: self.threshold as usize,
+                share_count: self.share_count,
+            });
         }
         let secret = GF2256::new(&new_from_slice(secret));
         let mut coeffs: Vec<GF2256> = vec![secret.clone()];
-        let mut buf = vec![0; 256];
         for _i in 0..self.threshold - 1 {
-            getrandom::getrandom(buf.as_mut_slice())?;
-            coeffs.push(GF2256::new(&new_from_slice(&buf)));
+            coeffs.push(GF2256::try_random(rng)?);
         }
         let secret_poly = Poly::new(coeffs);
-        let mut xs: Vec<GF2256> = Vec::new();
-        let mut ys = Vec::new();
-
-        for i in 1..self.share_count + 1 {
-            let x = GF2256::new(&new_from_slice(&i.to_le_bytes()));
-            ys.push(secret_poly.evaluate(&x));
-            xs.push(x);
-        }
+        let xs: Vec<GF2256> = (1..self.share_count + 1)
+            .map(|i| GF2256::new(&new_from_slice(&i.to_le_bytes())))
+            .collect();
+        let ys = evaluate_many(&secret_poly, &xs);
 
         let shares: Vec<Share> = xs
             .into_iter()
@@ -119,7 +105,20 @@ impl SecretSharer for ShamirSharer {
 
         Ok(shares)
     }
+}
+
+impl SecretSharer for ShamirSharer {
+    fn encode(&self, secret: &[u8]) -> Result<Vec<Share>, Error> {
+        self.encode_with_rng(secret, &mut OsRng)
+    }
+
     fn decode(&self, shares: &Vec<Share>) -> Result<Vec<u8>, Error> {
+        if shares.len() < self.threshold as usize {
+            return Err(Error::TooFewShares {
+                threshold: self.threshold as usize,
+                shares: shares.len(),
+            });
+        }
         let mut xs = Vec::new();
         let mut ys = Vec::new();
         for i in 0..shares.len() {
@@ -129,7 +128,7 @@ impl SecretSharer for ShamirSharer {
 
         let t = interpolate(&xs, &ys)?;
 
-        Ok(t.values[0].to_32_bytes())
+        Ok(t.coeff(0).to_32_bytes())
     }
 }
 
@@ -172,6 +171,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn duplicate_shares_are_an_error() -> Result<(), Error> {
+        let sharer = ShamirSharer::new(4, 2);
+        let t = sharer.encode("Hello".as_bytes())?;
+        assert_eq!(
+            sharer.decode(&vec![t[0].clone(), t[0].clone()]),
+            Err(Error::DuplicateX)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn errors_say_what_went_wrong() -> Result<(), Error> {
+        let sharer = ShamirSharer::new(4, 3);
+        let t = sharer.encode("Hello".as_bytes())?;
+        assert_eq!(
+            sharer.decode(&t[..2].to_vec()),
+            Err(Error::TooFewShares {
+                threshold: 3,
+                shares: 2
+            })
+        );
+        assert_eq!(
+            sharer.encode(&[0; 33]).unwrap_err(),
+            Error::SecretTooLong { len: 33, max: 32 }
+        );
+        assert_eq!(
+            ShamirSharer::new(2, 3).encode(&[1]).unwrap_err(),
+            Error::InvalidThreshold {
+                threshold: 3,
+                share_count: 2
+            }
+        );
+        assert_eq!(
+            Share::XorShare(vec![1]).get_x_shamir().unwrap_err(),
+            Error::WrongShareVariant
+        );
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn fails_under_thresold_test() {
@@ -219,4 +258,37 @@ mod tests {
         assert_eq!(secret, *(sharer.decode(&t)?));
         Ok(())
     }
+
+    // An rng whose source has gone away
+    struct FailingRng;
+
+    impl RngCore for FailingRng {
+        fn next_u32(&mut self) -> u32 {
+            panic!("FailingRng only fails")
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            panic!("FailingRng only fails")
+        }
+
+        fn fill_bytes(&mut self, _dest: &mut [u8]) {
+            panic!("FailingRng only fails")
+        }
+
+        fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            Err(getrandom::Error::UNSUPPORTED.into())
+        }
+    }
+
+    #[test]
+    fn rng_failure_is_an_error() {
+        let sharer = ShamirSharer::new(5, 3);
+        assert_eq!(
+            sharer.encode_with_rng(b"Hello", &mut FailingRng).err(),
+            Some(Error::Rng(getrandom::Error::UNSUPPORTED))
+        );
+        // a threshold of one draws nothing
+        let sharer = ShamirSharer::new(2, 1);
+        assert!(sharer.encode_with_rng(b"Hello", &mut FailingRng).is_ok());
+    }
 }
@@ -0,0 +1,344 @@
+use crate::algebra::pow;
+use crate::Poly;
+use crate::{Field, Ring};
+use std::collections::BTreeMap;
+
+// Struct that represents a polynomial by its non-zero terms only,
+// as (exponent, coefficient) pairs in order from lowest power to highest power
+#[derive(Clone, PartialEq)]
+pub struct SparsePoly<T> {
+    terms: Vec<(usize, T)>,
+}
+
+impl<T: Ring> SparsePoly<T> {
+    // Creates new SparsePoly from (exponent, coefficient) pairs in any order,
+    // combining equal exponents and dropping zero coefficients
+    pub fn new(terms: Vec<(usize, T)>) -> Self {
+        let mut combined: BTreeMap<usize, T> = BTreeMap::new();
+        for (exponent, coeff) in terms {
+            let sum = match combined.remove(&exponent) {
+                Some(old) => old + coeff,
+                None => coeff,
+            };
+            combined.insert(exponent, sum);
+        }
+        SparsePoly::from_map(combined)
+    }
+
+    fn from_map(map: BTreeMap<usize, T>) -> Self {
+        SparsePoly {
+            terms: map.into_iter().filter(|(_e, c)| !c.is_zero()).collect(),
+        }
+    }
+
+    pub fn terms(&self) -> &[(usize, T)] {
+        &self.terms
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    // Highest exponent, or None for the zero SparsePoly
+    pub fn degree(&self) -> Option<usize> {
+        self.terms.last().map(|(exponent, _coeff)| *exponent)
+    }
+
+    // Evaluates SparsePoly at a given T, with one exponentiation by squaring per term
+    pub fn evaluate(&self, value: &T) -> T {
+        let mut sum = T::zero();
+        for (exponent, coeff) in self.terms.iter() {
+            sum = sum + coeff.clone() * pow(value, *exponent);
+        }
+        sum
+    }
+}
+
+// The operations that divide coefficients
+impl<T: Field> SparsePoly<T> {
+    // Long division, with work proportional to the number of terms of the divisor
+    // for each term of the quotient
+    pub fn div(&self, poly2: &SparsePoly<T>) -> (Self, Self) {
+        let (div_deg, lc) = match poly2.terms.last() {
+            Some(term) => term.clone(),
+            None => panic!("Division by zero error"),
+        };
+        let mut remainder: BTreeMap<usize, T> = self.terms.iter().cloned().collect();
+        let mut quotient = Vec::new();
+        while let Some((&exponent, _coeff)) = remainder.last_key_value() {
+            if exponent < div_deg {
+                break;
+            }
+            let coeff = remainder.remove(&exponent).unwrap();
+            if coeff.is_zero() {
+                continue;
+            }
+            let t = coeff / lc.clone();
+            let shift = exponent - div_deg;
+            for (e, c) in poly2.terms[..poly2.terms.len() - 1].iter() {
+                let old = remainder.remove(&(e + shift)).unwrap_or_else(T::zero);
+                remainder.insert(e + shift, old - t.clone() * c.clone());
+            }
+            quotient.push((shift, t));
+        }
+        (SparsePoly::new(quotient), SparsePoly::from_map(remainder))
+    }
+
+    // mod function for SparsePoly
+    pub fn modulus(&self, poly2: &SparsePoly<T>) -> SparsePoly<T> {
+        self.div(poly2).1
+    }
+
+    // Reduces a dense Poly mod self in time proportional to
+    // deg poly * (number of terms of self), rather than deg poly * deg self
+    pub fn reduce(&self, poly: &Poly<T>) -> Poly<T> {
+        let (div_deg, lc) = match self.terms.last() {
+            Some(term) => term.clone(),
+            None => panic!("Division by zero error"),
+        };
+        let mut values = poly.values.clone();
+        if values.len() <= div_deg {
+            return Poly::new(values);
+        }
+        for x in (div_deg..values.len()).rev() {
+            if values[x].is_zero() {
+                continue;
+            }
+            let t = values[x].clone() / lc.clone();
+            for (e, c) in self.terms[..self.terms.len() - 1].iter() {
+                let y = x - div_deg + e;
+                values[y] = values[y].clone() - t.clone() * c.clone();
+            }
+        }
+        values.truncate(div_deg);
+        Poly::new(values)
+    }
+
+    // self^exponent mod modulus, by squaring dense Polys and reducing them with
+    // the (sparse) modulus
+    pub fn pow_mod(&self, exponent: usize, modulus: &SparsePoly<T>) -> SparsePoly<T> {
+        let base = modulus.reduce(&Poly::from(self.clone()));
+        let mut result = modulus.reduce(&Poly::new(vec![T::one()]));
+        for bit in (0..usize::BITS - exponent.leading_zeros()).rev() {
+            result = modulus.reduce(&(&result * &result));
+            if (exponent >> bit) & 1 == 1 {
+                result = modulus.reduce(&(result * &base));
+            }
+        }
+        SparsePoly::from(result)
+    }
+}
+
+impl<T: Ring> From<Poly<T>> for SparsePoly<T> {
+    fn from(poly: Poly<T>) -> Self {
+        SparsePoly {
+            terms: poly
+                .values
+                .into_iter()
+                .enumerate()
+                .filter(|(_e, c)| !c.is_zero())
+                .collect(),
+        }
+    }
+}
+
+impl<T: Ring> From<SparsePoly<T>> for Poly<T> {
+    fn from(poly: SparsePoly<T>) -> Self {
+        let len = poly.degree().map_or(0, |degree| degree + 1);
+        let mut values = vec![T::zero(); len];
+        for (exponent, coeff) in poly.terms {
+            values[exponent] = coeff;
+        }
+        Poly::new(values)
+    }
+}
+
+impl<T: Ring> std::ops::Add for SparsePoly<T> {
+    type Output = SparsePoly<T>;
+
+    fn add(self, rhs: SparsePoly<T>) -> Self {
+        let mut terms = self.terms;
+        terms.extend(rhs.terms);
+        SparsePoly::new(terms)
+    }
+}
+
+impl<T: Ring> std::ops::Sub for SparsePoly<T> {
+    type Output = SparsePoly<T>;
+
+    fn sub(self, rhs: SparsePoly<T>) -> Self {
+        let mut terms = self.terms;
+        terms.extend(rhs.terms.into_iter().map(|(e, c)| (e, T::zero() - c)));
+        SparsePoly::new(terms)
+    }
+}
+
+impl<T: Ring> std::ops::Mul for SparsePoly<T> {
+    type Output = SparsePoly<T>;
+
+    fn mul(self, rhs: SparsePoly<T>) -> Self {
+        let mut terms = Vec::with_capacity(self.terms.len() * rhs.terms.len());
+        for (e1, c1) in self.terms.iter() {
+            for (e2, c2) in rhs.terms.iter() {
+                terms.push((e1 + e2, c1.clone() * c2.clone()));
+            }
+        }
+        SparsePoly::new(terms)
+    }
+}
+
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Display
+    for SparsePoly<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        if self.terms.is_empty() {
+            return write!(f, "{}", T::zero());
+        }
+        for (i, (exponent, coeff)) in self.terms.iter().rev().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            match (*exponent, coeff.is_one()) {
+                (0, _) => write!(f, "{}", coeff)?,
+                (1, true) => write!(f, "x")?,
+                (1, false) => write!(f, "{}x", coeff)?,
+                (_, true) => write!(f, "x^{}", exponent)?,
+                (_, false) => write!(f, "{}x^{}", coeff, exponent)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Display + num::Zero + Clone + num::One + PartialEq> std::fmt::Debug
+    for SparsePoly<T>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_integer_slice, new_from_slice};
+    use num::rational::Rational64;
+    use z2z::Z2z;
+
+    fn q(terms: &[(usize, i64)]) -> SparsePoly<Rational64> {
+        SparsePoly::new(
+            terms
+                .iter()
+                .map(|&(e, c)| (e, Rational64::from_integer(c)))
+                .collect(),
+        )
+    }
+
+    fn z(exponents: &[usize]) -> SparsePoly<Z2z> {
+        SparsePoly::new(exponents.iter().map(|&e| (e, Z2z::One)).collect())
+    }
+
+    #[test]
+    fn new_combines_terms() {
+        let t = q(&[(65536, 1), (3, 2), (0, 1), (3, -2)]);
+        assert_eq!(
+            t.terms(),
+            &[
+                (0, Rational64::from_integer(1)),
+                (65536, Rational64::from_integer(1))
+            ]
+        );
+        assert_eq!(t.degree(), Some(65536));
+        assert!(q(&[(4, 1), (4, -1)]).is_zero());
+        assert_eq!(q(&[]).degree(), None);
+    }
+
+    #[test]
+    fn poly_round_trip() {
+        let dense = from_integer_slice(&vec![1, 0, 0, -3, 0, 2]);
+        let sparse = SparsePoly::from(dense.clone());
+        assert_eq!(sparse, q(&[(0, 1), (3, -3), (5, 2)]));
+        assert_eq!(Poly::from(sparse), dense);
+        assert_eq!(Poly::from(q(&[])), from_integer_slice(&vec![0]));
+    }
+
+    #[test]
+    fn add_sub_mul() {
+        let a = q(&[(1000, 1), (1, 1)]);
+        let b = q(&[(1000, 1), (0, -1)]);
+        assert_eq!(a.clone() - b.clone(), q(&[(1, 1), (0, 1)]));
+        assert_eq!(a.clone() + b.clone(), q(&[(1000, 2), (1, 1), (0, -1)]));
+        assert_eq!(a * b, q(&[(2000, 1), (1001, 1), (1000, -1), (1, -1)]));
+    }
+
+    #[test]
+    fn div_matches_dense() {
+        let a = q(&[(40, 3), (17, -1), (2, 5), (0, 1)]);
+        let b = q(&[(7, 2), (1, 1), (0, -1)]);
+        let (quotient, remainder) = a.div(&b);
+        let (dense_quotient, dense_remainder) = Poly::from(a.clone()).div(&Poly::from(b.clone()));
+        assert_eq!(Poly::from(quotient), dense_quotient);
+        assert_eq!(Poly::from(remainder.clone()), dense_remainder);
+        assert_eq!(a.modulus(&b), remainder);
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero error")]
+    fn div_by_zero() {
+        let _ = q(&[(1, 1)]).div(&q(&[]));
+    }
+
+    #[test]
+    fn reduce_by_pentanomial() {
+        // x^256 + x^10 + x^5 + x^2 + 1, the GF2256 modulus
+        let irred = z(&[256, 10, 5, 2, 0]);
+        let dense: Vec<u8> = (0..64).map(|i| (i * 37 + 11) as u8).collect();
+        let dense = new_from_slice(&dense);
+        assert_eq!(
+            irred.reduce(&dense),
+            dense.modulus(&Poly::from(irred.clone()))
+        );
+    }
+
+    #[test]
+    fn pow_mod_frobenius() {
+        // x^8 + x^4 + x^3 + x + 1 is irreducible, so x^(2^8) = x in GF(2^8)
+        let modulus = z(&[8, 4, 3, 1, 0]);
+        let x = z(&[1]);
+        assert_eq!(x.pow_mod(256, &modulus), x);
+        assert_ne!(x.pow_mod(16, &modulus), x);
+        assert_eq!(x.pow_mod(0, &modulus), z(&[0]));
+    }
+
+    #[test]
+    fn pow_mod_matches_repeated_mul() {
+        let base = q(&[(3, 1), (0, 2)]);
+        let modulus = q(&[(5, 1), (2, -1), (0, 3)]);
+        let mut expected = q(&[(0, 1)]);
+        for _i in 0..13 {
+            expected = (expected * base.clone()).modulus(&modulus);
+        }
+        assert_eq!(base.pow_mod(13, &modulus), expected);
+    }
+
+    #[test]
+    fn evaluate_sparse() {
+        let t = q(&[(62, 1), (3, 1), (0, 1)]);
+        assert_eq!(
+            t.evaluate(&Rational64::from_integer(1)),
+            Rational64::from_integer(3)
+        );
+        assert_eq!(
+            t.evaluate(&Rational64::from_integer(-1)),
+            Rational64::from_integer(1)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", z(&[65536, 3, 0])), "x^65536 + x^3 + 1");
+        assert_eq!(format!("{}", q(&[(1, 2), (0, -1)])), "2x + -1");
+        assert_eq!(format!("{}", q(&[])), "0");
+    }
+}
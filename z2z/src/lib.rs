@@ -1,8 +1,18 @@
 use std::ops::Deref;
+use std::str::FromStr;
 
+mod matrix;
+mod vector;
+pub use matrix::Z2Matrix;
+pub use vector::Z2Vec;
+
+use rand_core::RngCore;
 use serde::{Deserialize, Serialize};
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
 pub enum Z2z {
+    #[default]
     Zero,
     One,
 }
@@ -86,6 +96,193 @@ impl std::ops::Div for Z2z {
     }
 }
 
+// In a field the remainder is always zero, but division by Zero still panics
+impl std::ops::Rem for Z2z {
+    type Output = Self;
+
+    fn rem(self, z2z2: Z2z) -> Self::Output {
+        match z2z2 {
+            Z2z::Zero => panic!("division by Z2z::Zero"),
+            Z2z::One => Z2z::Zero,
+        }
+    }
+}
+
+// -x = x, since 1 + 1 = 0
+impl std::ops::Neg for Z2z {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+// Xor is the same as addition
+impl std::ops::BitXor for Z2z {
+    type Output = Self;
+
+    fn bitxor(self, z2z2: Z2z) -> Self::Output {
+        match (self, z2z2) {
+            (Z2z::Zero, Z2z::Zero) => Z2z::Zero,
+            (Z2z::Zero, Z2z::One) => Z2z::One,
+            (Z2z::One, Z2z::Zero) => Z2z::One,
+            (Z2z::One, Z2z::One) => Z2z::Zero,
+        }
+    }
+}
+
+// And is the same as multiplication
+impl std::ops::BitAnd for Z2z {
+    type Output = Self;
+
+    fn bitand(self, z2z2: Z2z) -> Self::Output {
+        match (self, z2z2) {
+            (Z2z::One, Z2z::One) => Z2z::One,
+            _ => Z2z::Zero,
+        }
+    }
+}
+
+// Not is adding one
+impl std::ops::Not for Z2z {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Z2z::Zero => Z2z::One,
+            Z2z::One => Z2z::Zero,
+        }
+    }
+}
+
+impl From<bool> for Z2z {
+    fn from(bit: bool) -> Self {
+        match bit {
+            true => Z2z::One,
+            false => Z2z::Zero,
+        }
+    }
+}
+
+impl From<Z2z> for bool {
+    fn from(z2z: Z2z) -> Self {
+        z2z == Z2z::One
+    }
+}
+
+impl std::iter::Sum for Z2z {
+    fn sum<I: Iterator<Item = Z2z>>(iter: I) -> Self {
+        iter.fold(Z2z::Zero, |sum, x| sum + x)
+    }
+}
+
+impl<'a> std::iter::Sum<&'a Z2z> for Z2z {
+    fn sum<I: Iterator<Item = &'a Z2z>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl std::iter::Product for Z2z {
+    fn product<I: Iterator<Item = Z2z>>(iter: I) -> Self {
+        iter.fold(Z2z::One, |product, x| product * x)
+    }
+}
+
+impl<'a> std::iter::Product<&'a Z2z> for Z2z {
+    fn product<I: Iterator<Item = &'a Z2z>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl Z2z {
+    // The multiplicative inverse, or None for Zero
+    pub fn checked_inv(&self) -> Option<Z2z> {
+        match self {
+            Z2z::Zero => None,
+            Z2z::One => Some(Z2z::One),
+        }
+    }
+
+    // Zero or One with equal probability
+    pub fn random(rng: &mut impl RngCore) -> Z2z {
+        Z2z::from(rng.next_u32() & 1 == 1)
+    }
+}
+
+impl num::CheckedDiv for Z2z {
+    fn checked_div(&self, z2z2: &Z2z) -> Option<Z2z> {
+        Some(*self * z2z2.checked_inv()?)
+    }
+}
+
+impl num::traits::Inv for Z2z {
+    type Output = Self;
+
+    fn inv(self) -> Self::Output {
+        match self.checked_inv() {
+            Some(inverse) => inverse,
+            None => panic!("division by Z2z::Zero"),
+        }
+    }
+}
+
+// x^0 = 1 (even for x = 0) and x^n = x otherwise
+macro_rules! impl_pow {
+    ($($exponent:ty),*) => {
+        $(
+            impl num::traits::Pow<$exponent> for Z2z {
+                type Output = Self;
+
+                fn pow(self, exponent: $exponent) -> Self::Output {
+                    match exponent {
+                        0 => Z2z::One,
+                        _ => self,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_pow!(u8, u16, u32, u64, usize);
+
+// Error from parsing anything but "0" or "1" as a Z2z
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseZ2zError;
+
+impl std::fmt::Display for ParseZ2zError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "a Z2z is either 0 or 1")
+    }
+}
+
+impl std::error::Error for ParseZ2zError {}
+
+// Parses the output of Display
+impl FromStr for Z2z {
+    type Err = ParseZ2zError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Z2z::Zero),
+            "1" => Ok(Z2z::One),
+            _ => Err(ParseZ2zError),
+        }
+    }
+}
+
+impl num::Num for Z2z {
+    type FromStrRadixErr = ParseZ2zError;
+
+    // 0 and 1 are written the same way in every radix
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseZ2zError);
+        }
+        s.parse()
+    }
+}
+
 impl std::fmt::Display for Z2z {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
@@ -166,6 +363,112 @@ mod tests {
         assert_eq!(Z2z::Zero / Z2z::One, Z2z::Zero);
     }
 
+    #[test]
+    fn checked_div_and_inv() {
+        use num::{traits::Inv, CheckedDiv};
+        assert_eq!(Z2z::One.checked_div(&Z2z::Zero), None);
+        assert_eq!(Z2z::Zero.checked_div(&Z2z::One), Some(Z2z::Zero));
+        assert_eq!(Z2z::Zero.checked_inv(), None);
+        assert_eq!(Z2z::One.inv(), Z2z::One);
+    }
+
+    #[test]
+    fn bitwise_ops_are_field_ops() {
+        for a in [Z2z::Zero, Z2z::One] {
+            assert_eq!(!a, a + Z2z::One);
+            assert_eq!(-a, a);
+            for b in [Z2z::Zero, Z2z::One] {
+                assert_eq!(a ^ b, a + b);
+                assert_eq!(a & b, a * b);
+                assert_eq!(bool::from(a) ^ bool::from(b), bool::from(a ^ b));
+                assert_eq!(bool::from(a) & bool::from(b), bool::from(a & b));
+            }
+        }
+    }
+
+    #[test]
+    fn bool_conversions() {
+        assert_eq!(Z2z::from(true), Z2z::One);
+        assert_eq!(Z2z::from(false), Z2z::Zero);
+        let bit: bool = Z2z::One.into();
+        assert!(bit);
+        assert_eq!(Z2z::default(), Z2z::Zero);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let bits = [Z2z::One, Z2z::One, Z2z::One];
+        assert_eq!(bits.iter().sum::<Z2z>(), Z2z::One);
+        assert_eq!(bits[..2].iter().copied().sum::<Z2z>(), Z2z::Zero);
+        assert_eq!(bits.iter().product::<Z2z>(), Z2z::One);
+        assert_eq!(
+            [Z2z::One, Z2z::Zero].into_iter().product::<Z2z>(),
+            Z2z::Zero
+        );
+        assert_eq!(std::iter::empty::<Z2z>().sum::<Z2z>(), Z2z::Zero);
+        assert_eq!(std::iter::empty::<Z2z>().product::<Z2z>(), Z2z::One);
+    }
+
+    #[test]
+    fn parse() {
+        use num::Num;
+        assert_eq!("1".parse(), Ok(Z2z::One));
+        assert_eq!(Z2z::Zero.to_string().parse(), Ok(Z2z::Zero));
+        assert_eq!("2".parse::<Z2z>(), Err(ParseZ2zError));
+        assert_eq!(" 1".parse::<Z2z>(), Err(ParseZ2zError));
+        assert_eq!(Z2z::from_str_radix("1", 16), Ok(Z2z::One));
+        assert_eq!(Z2z::from_str_radix("1", 1), Err(ParseZ2zError));
+    }
+
+    #[test]
+    fn pow_and_rem() {
+        use num::traits::Pow;
+        assert_eq!(Z2z::Zero.pow(0u32), Z2z::One);
+        assert_eq!(Z2z::Zero.pow(5u8), Z2z::Zero);
+        assert_eq!(Z2z::One.pow(usize::MAX), Z2z::One);
+        assert_eq!(Z2z::One % Z2z::One, Z2z::Zero);
+    }
+
+    #[test]
+    #[should_panic(expected = "division by Z2z::Zero")]
+    fn rem_by_zero_panics() {
+        let _ = Z2z::One % Z2z::Zero;
+    }
+
+    // Works with code that is generic over num's traits
+    #[test]
+    fn generic_num_code() {
+        fn sum_of_squares<T: num::Num + Copy>(values: &[T]) -> T {
+            values.iter().fold(T::zero(), |sum, &x| sum + x * x)
+        }
+        assert_eq!(sum_of_squares(&[Z2z::One, Z2z::One, Z2z::One]), Z2z::One);
+    }
+
+    #[test]
+    fn random_takes_both_values() {
+        // a fixed sequence of alternating parity, standing in for a real RNG
+        struct Counter(u32);
+        impl RngCore for Counter {
+            fn next_u32(&mut self) -> u32 {
+                self.0 += 1;
+                self.0
+            }
+            fn next_u64(&mut self) -> u64 {
+                self.next_u32() as u64
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest)
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+        let mut rng = Counter(0);
+        assert_eq!(Z2z::random(&mut rng), Z2z::One);
+        assert_eq!(Z2z::random(&mut rng), Z2z::Zero);
+    }
+
     #[test]
     fn symmetric() {
         assert_eq!(Z2z::Zero + Z2z::One, Z2z::One + Z2z::Zero);
@@ -0,0 +1,385 @@
+use crate::{Z2Vec, Z2z};
+use serde::{Deserialize, Serialize};
+
+// Method of Four Russians multiplication precomputes the 2^k sums of each block of
+// k rows of the right hand side; 8 keeps the tables small enough to stay in cache
+const M4RM_BLOCK: usize = 8;
+
+// A matrix over Z2z, stored as packed rows so row operations work a word at a time
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Z2Matrix {
+    cols: usize,
+    rows: Vec<Z2Vec>,
+}
+
+impl Z2Matrix {
+    pub fn zeros(nrows: usize, ncols: usize) -> Self {
+        Z2Matrix {
+            cols: ncols,
+            rows: vec![Z2Vec::zeros(ncols); nrows],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        Z2Matrix {
+            cols: n,
+            rows: (0..n).map(|i| Z2Vec::unit(n, i)).collect(),
+        }
+    }
+
+    // Builds a matrix from rows of equal length, with ncols for when there are no rows
+    pub fn from_rows(ncols: usize, rows: Vec<Z2Vec>) -> Self {
+        if rows.iter().any(|row| row.len() != ncols) {
+            panic!("Mismatched dimensions");
+        }
+        Z2Matrix { cols: ncols, rows }
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> &[Z2Vec] {
+        &self.rows
+    }
+
+    pub fn row(&self, i: usize) -> &Z2Vec {
+        &self.rows[i]
+    }
+
+    pub fn column(&self, j: usize) -> Z2Vec {
+        self.rows.iter().map(|row| row.get(j)).collect()
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Z2z {
+        self.rows[i].get(j)
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: Z2z) {
+        self.rows[i].set(j, value);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.rows.iter().all(|row| row.is_zero())
+    }
+
+    pub fn transpose(&self) -> Self {
+        Z2Matrix {
+            cols: self.nrows(),
+            rows: (0..self.cols).map(|j| self.column(j)).collect(),
+        }
+    }
+
+    // [self | other], the columns of other after those of self
+    pub fn augment(&self, other: &Z2Matrix) -> Self {
+        if self.nrows() != other.nrows() {
+            panic!("Mismatched dimensions");
+        }
+        Z2Matrix {
+            cols: self.cols + other.cols,
+            rows: self
+                .rows
+                .iter()
+                .zip(other.rows.iter())
+                .map(|(a, b)| a.concat(b))
+                .collect(),
+        }
+    }
+
+    // The columns start..end as a new matrix
+    pub fn columns(&self, start: usize, end: usize) -> Self {
+        Z2Matrix {
+            cols: end - start,
+            rows: self.rows.iter().map(|row| row.slice(start, end)).collect(),
+        }
+    }
+
+    // self * vector, one dot product per row
+    pub fn mul_vec(&self, vector: &Z2Vec) -> Z2Vec {
+        if vector.len() != self.cols {
+            panic!("Mismatched dimensions");
+        }
+        self.rows.iter().map(|row| row.dot(vector)).collect()
+    }
+
+    // Matrix product by the Method of Four Russians: for each block of k rows of rhs,
+    // tabulate all 2^k of their sums, then add one table entry per row of self,
+    // for O(n^3 / k) word operations rather than O(n^3)
+    pub fn mul_m4rm(&self, rhs: &Z2Matrix, k: usize) -> Self {
+        if self.cols != rhs.nrows() {
+            panic!("Mismatched dimensions");
+        }
+        if k == 0 || k > 16 {
+            panic!("M4RM block size must be between 1 and 16");
+        }
+        let mut result = Z2Matrix::zeros(self.nrows(), rhs.cols);
+        for start in (0..self.cols).step_by(k) {
+            let block = k.min(self.cols - start);
+            let mut table = vec![Z2Vec::zeros(rhs.cols); 1 << block];
+            for i in 1..table.len() {
+                // each sum is a smaller sum plus the row for its lowest set bit
+                let lowest = i.trailing_zeros() as usize;
+                table[i] = &table[i & (i - 1)] + &rhs.rows[start + lowest];
+            }
+            for (row, target) in self.rows.iter().zip(result.rows.iter_mut()) {
+                let index = row.bits(start, block) as usize;
+                if index != 0 {
+                    *target += &table[index];
+                }
+            }
+        }
+        result
+    }
+
+    // The reduced row echelon form and its pivot columns, by Gauss-Jordan elimination
+    pub fn rref(&self) -> (Self, Vec<usize>) {
+        let mut reduced = self.clone();
+        let mut pivots = Vec::new();
+        for col in 0..self.cols {
+            let row = pivots.len();
+            if row == reduced.nrows() {
+                break;
+            }
+            let pivot = match (row..reduced.nrows()).find(|&i| reduced.get(i, col) == Z2z::One) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+            reduced.rows.swap(row, pivot);
+            let pivot_row = reduced.rows[row].clone();
+            for (i, other) in reduced.rows.iter_mut().enumerate() {
+                if i != row && other.get(col) == Z2z::One {
+                    *other += &pivot_row;
+                }
+            }
+            pivots.push(col);
+        }
+        (reduced, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rref().1.len()
+    }
+
+    // Some x with self * x = rhs, with the free variables zero, or None if there isn't one
+    pub fn solve(&self, rhs: &Z2Vec) -> Option<Z2Vec> {
+        if rhs.len() != self.nrows() {
+            panic!("Mismatched dimensions");
+        }
+        let rhs_column =
+            Z2Matrix::from_rows(1, rhs.iter().map(|x| Z2Vec::from_iter([x])).collect());
+        let (reduced, pivots) = self.augment(&rhs_column).rref();
+        // a pivot in the last column is the equation 0 = 1
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+        let mut solution = Z2Vec::zeros(self.cols);
+        for (row, col) in pivots.into_iter().enumerate() {
+            solution.set(col, reduced.get(row, self.cols));
+        }
+        Some(solution)
+    }
+
+    // The inverse of a square matrix, or None if it is singular
+    pub fn inverse(&self) -> Option<Self> {
+        if self.nrows() != self.cols {
+            panic!("Only square matrices have inverses");
+        }
+        let n = self.cols;
+        let (reduced, pivots) = self.augment(&Z2Matrix::identity(n)).rref();
+        // [self | I] always has rank n, and self is invertible when its pivots are all on the left
+        if !pivots.into_iter().eq(0..n) {
+            return None;
+        }
+        Some(reduced.columns(n, 2 * n))
+    }
+
+    // A basis of the kernel {x : self * x = 0}, one vector per free column
+    pub fn kernel(&self) -> Vec<Z2Vec> {
+        let (reduced, pivots) = self.rref();
+        let mut basis = Vec::new();
+        let mut pivot_iter = pivots.iter().peekable();
+        for free in 0..self.cols {
+            if pivot_iter.peek() == Some(&&free) {
+                pivot_iter.next();
+                continue;
+            }
+            // set the free variable to one and solve for the pivot variables
+            let mut vector = Z2Vec::unit(self.cols, free);
+            for (row, col) in pivots.iter().enumerate() {
+                vector.set(*col, reduced.get(row, free));
+            }
+            basis.push(vector);
+        }
+        basis
+    }
+}
+
+impl std::ops::Add for &Z2Matrix {
+    type Output = Z2Matrix;
+
+    fn add(self, rhs: &Z2Matrix) -> Self::Output {
+        if self.nrows() != rhs.nrows() || self.cols != rhs.cols {
+            panic!("Mismatched dimensions");
+        }
+        Z2Matrix {
+            cols: self.cols,
+            rows: self
+                .rows
+                .iter()
+                .zip(rhs.rows.iter())
+                .map(|(a, b)| a + b)
+                .collect(),
+        }
+    }
+}
+
+impl std::ops::Mul for &Z2Matrix {
+    type Output = Z2Matrix;
+
+    fn mul(self, rhs: &Z2Matrix) -> Self::Output {
+        self.mul_m4rm(rhs, M4RM_BLOCK)
+    }
+}
+
+impl std::ops::Mul for Z2Matrix {
+    type Output = Z2Matrix;
+
+    fn mul(self, rhs: Z2Matrix) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+// One line of 0s and 1s per row
+impl std::fmt::Display for Z2Matrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn matrix(rows: &[&str]) -> Z2Matrix {
+        let rows: Vec<Z2Vec> = rows
+            .iter()
+            .map(|row| row.chars().map(|c| Z2z::from(c == '1')).collect())
+            .collect();
+        Z2Matrix::from_rows(rows[0].len(), rows)
+    }
+
+    fn random_matrix(nrows: usize, ncols: usize) -> impl Strategy<Value = Z2Matrix> {
+        let row = proptest::collection::vec(any::<u64>(), ncols.div_ceil(64))
+            .prop_map(move |words| Z2Vec::from_words(ncols, words));
+        proptest::collection::vec(row, nrows).prop_map(move |rows| Z2Matrix::from_rows(ncols, rows))
+    }
+
+    // a product of random shapes, with the inner dimensions agreeing
+    fn random_product() -> impl Strategy<Value = (Z2Matrix, Z2Matrix)> {
+        (1..80usize, 1..140usize, 1..80usize)
+            .prop_flat_map(|(m, n, p)| (random_matrix(m, n), random_matrix(n, p)))
+    }
+
+    // the definition, one dot product per entry
+    fn naive_mul(a: &Z2Matrix, b: &Z2Matrix) -> Z2Matrix {
+        let columns = b.transpose();
+        let rows = a
+            .rows()
+            .iter()
+            .map(|row| columns.rows().iter().map(|col| row.dot(col)).collect())
+            .collect();
+        Z2Matrix::from_rows(b.ncols(), rows)
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn m4rm_matches_naive((a, b) in random_product()) {
+            let expected = naive_mul(&a, &b);
+            prop_assert_eq!(&a * &b, expected.clone());
+            for k in [1, 3, 16] {
+                prop_assert_eq!(a.mul_m4rm(&b, k), expected.clone());
+            }
+        }
+
+        #[test]
+        fn random_inverse(a in random_matrix(40, 40)) {
+            match a.inverse() {
+                Some(inverse) => {
+                    prop_assert_eq!(&a * &inverse, Z2Matrix::identity(40));
+                    prop_assert_eq!(&inverse * &a, Z2Matrix::identity(40));
+                }
+                None => prop_assert!(a.rank() < 40),
+            }
+        }
+
+        #[test]
+        fn kernel(a in random_matrix(30, 50)) {
+            let kernel = a.kernel();
+            prop_assert_eq!(kernel.len(), 50 - a.rank());
+            for vector in kernel.iter() {
+                prop_assert!(a.mul_vec(vector).is_zero());
+            }
+            let basis = Z2Matrix::from_rows(50, kernel);
+            prop_assert_eq!(basis.rank(), basis.nrows());
+        }
+    }
+
+    #[test]
+    fn identity_and_transpose() {
+        let a = matrix(&["101", "011"]);
+        assert_eq!(&Z2Matrix::identity(2) * &a, a);
+        assert_eq!(&a * &Z2Matrix::identity(3), a);
+        assert_eq!(a.transpose(), matrix(&["10", "01", "11"]));
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(a.to_string(), "101\n011");
+    }
+
+    #[test]
+    fn rref_and_rank() {
+        let a = matrix(&["110", "011", "101"]);
+        let (reduced, pivots) = a.rref();
+        assert_eq!(reduced, matrix(&["101", "011", "000"]));
+        assert_eq!(pivots, vec![0, 1]);
+        assert_eq!(a.rank(), 2);
+        assert_eq!(Z2Matrix::zeros(3, 4).rank(), 0);
+        assert_eq!(Z2Matrix::identity(100).rank(), 100);
+    }
+
+    #[test]
+    fn solve() {
+        let a = matrix(&["110", "011", "101"]);
+        let b: Z2Vec = [Z2z::One, Z2z::One, Z2z::Zero].into_iter().collect();
+        let x = a.solve(&b).unwrap();
+        assert_eq!(a.mul_vec(&x), b);
+        // the rows sum to zero, so the entries of the right hand side must too
+        let inconsistent: Z2Vec = [Z2z::One, Z2z::Zero, Z2z::Zero].into_iter().collect();
+        assert_eq!(a.solve(&inconsistent), None);
+    }
+
+    #[test]
+    fn inverse() {
+        // unit upper triangular, so the inverse is I + N + N^2 for the part N above the diagonal
+        let a = matrix(&["110", "011", "001"]);
+        assert_eq!(a.inverse(), Some(matrix(&["111", "011", "001"])));
+        assert_eq!(
+            Z2Matrix::identity(64).inverse(),
+            Some(Z2Matrix::identity(64))
+        );
+        assert_eq!(matrix(&["11", "11"]).inverse(), None);
+        assert_eq!(Z2Matrix::zeros(0, 0).inverse(), Some(Z2Matrix::zeros(0, 0)));
+        assert!(Z2Matrix::identity(5).kernel().is_empty());
+    }
+}
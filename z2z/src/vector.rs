@@ -0,0 +1,347 @@
+use crate::Z2z;
+use serde::{Deserialize, Serialize};
+
+// A vector over Z2z, packed 64 entries to a word: entry i is bit i % 64 of word i / 64.
+// The bits past len are always zero, so equal vectors have equal words.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Z2Vec {
+    len: usize,
+    words: Vec<u64>,
+}
+
+fn word_count(len: usize) -> usize {
+    len.div_ceil(64)
+}
+
+impl Z2Vec {
+    // The zero vector of the given length
+    pub fn zeros(len: usize) -> Self {
+        Z2Vec {
+            len,
+            words: vec![0; word_count(len)],
+        }
+    }
+
+    // The vector with a single one, at index
+    pub fn unit(len: usize, index: usize) -> Self {
+        let mut unit = Z2Vec::zeros(len);
+        unit.set(index, Z2z::One);
+        unit
+    }
+
+    // Builds a vector from packed words, ignoring any bits past len
+    pub fn from_words(len: usize, mut words: Vec<u64>) -> Self {
+        words.resize(word_count(len), 0);
+        let mut vector = Z2Vec { len, words };
+        vector.clear_tail();
+        vector
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Z2z {
+        self.check_index(index);
+        Z2z::from((self.words[index / 64] >> (index % 64)) & 1 == 1)
+    }
+
+    pub fn set(&mut self, index: usize, value: Z2z) {
+        self.check_index(index);
+        let mask = 1 << (index % 64);
+        match value {
+            Z2z::One => self.words[index / 64] |= mask,
+            Z2z::Zero => self.words[index / 64] &= !mask,
+        }
+    }
+
+    // Adds one to the entry at index
+    pub fn flip(&mut self, index: usize) {
+        self.check_index(index);
+        self.words[index / 64] ^= 1 << (index % 64);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    // The number of ones (the Hamming weight)
+    pub fn weight(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    // The sum of the products of the entries, i.e. the parity of self & other
+    pub fn dot(&self, other: &Z2Vec) -> Z2z {
+        self.check_len(other);
+        let ones: u32 = self
+            .words
+            .iter()
+            .zip(other.words.iter())
+            .map(|(a, b)| (a & b).count_ones())
+            .sum();
+        Z2z::from(ones % 2 == 1)
+    }
+
+    // The index of the first one at or after start
+    pub fn first_one(&self, start: usize) -> Option<usize> {
+        if start >= self.len {
+            return None;
+        }
+        let mut word = start / 64;
+        let mut bits = self.words[word] & (u64::MAX << (start % 64));
+        loop {
+            if bits != 0 {
+                return Some(word * 64 + bits.trailing_zeros() as usize);
+            }
+            word += 1;
+            bits = *self.words.get(word)?;
+        }
+    }
+
+    // The count (at most 64) entries from start as the low bits of a u64,
+    // lowest index first. Entries past the end read as zero.
+    pub fn bits(&self, start: usize, count: usize) -> u64 {
+        if count == 0 || start >= self.len {
+            return 0;
+        }
+        let (word, shift) = (start / 64, start % 64);
+        let mut bits = self.words[word] >> shift;
+        if shift > 0 && word + 1 < self.words.len() {
+            bits |= self.words[word + 1] << (64 - shift);
+        }
+        match count {
+            64.. => bits,
+            _ => bits & ((1 << count) - 1),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Z2z> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    // The entries start..end as a new vector
+    pub fn slice(&self, start: usize, end: usize) -> Z2Vec {
+        if start > end || end > self.len {
+            panic!("Slice out of range");
+        }
+        let words = (start..end).step_by(64).map(|i| self.bits(i, 64)).collect();
+        Z2Vec::from_words(end - start, words)
+    }
+
+    // self followed by other
+    pub fn concat(&self, other: &Z2Vec) -> Z2Vec {
+        let mut result = Z2Vec::zeros(self.len + other.len);
+        result.words[..self.words.len()].copy_from_slice(&self.words);
+        let shift = self.len % 64;
+        for (i, word) in other.words.iter().enumerate() {
+            let target = self.len / 64 + i;
+            result.words[target] |= word << shift;
+            if shift > 0 && target + 1 < result.words.len() {
+                result.words[target + 1] |= word >> (64 - shift);
+            }
+        }
+        result
+    }
+
+    fn clear_tail(&mut self) {
+        if !self.len.is_multiple_of(64) {
+            let last = self.words.len() - 1;
+            self.words[last] &= (1 << (self.len % 64)) - 1;
+        }
+    }
+
+    // Adds rhs word by word
+    fn xor_words(&mut self, rhs: &Z2Vec) {
+        self.check_len(rhs);
+        for (word, x) in self.words.iter_mut().zip(rhs.words.iter()) {
+            *word ^= x;
+        }
+    }
+
+    fn check_index(&self, index: usize) {
+        if index >= self.len {
+            panic!("Index {} out of range for length {}", index, self.len);
+        }
+    }
+
+    fn check_len(&self, other: &Z2Vec) {
+        if self.len != other.len {
+            panic!("Mismatched lengths");
+        }
+    }
+}
+
+impl FromIterator<Z2z> for Z2Vec {
+    fn from_iter<I: IntoIterator<Item = Z2z>>(iter: I) -> Self {
+        let mut words = Vec::new();
+        let mut len = 0;
+        for value in iter {
+            if len % 64 == 0 {
+                words.push(0);
+            }
+            if value == Z2z::One {
+                words[len / 64] |= 1 << (len % 64);
+            }
+            len += 1;
+        }
+        Z2Vec { len, words }
+    }
+}
+
+// Addition is xor of the words
+impl std::ops::AddAssign<&Z2Vec> for Z2Vec {
+    fn add_assign(&mut self, rhs: &Z2Vec) {
+        self.xor_words(rhs);
+    }
+}
+
+impl std::ops::Add<&Z2Vec> for Z2Vec {
+    type Output = Z2Vec;
+
+    fn add(mut self, rhs: &Z2Vec) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl std::ops::Add for &Z2Vec {
+    type Output = Z2Vec;
+
+    fn add(self, rhs: &Z2Vec) -> Self::Output {
+        self.clone() + rhs
+    }
+}
+
+impl std::ops::Add for Z2Vec {
+    type Output = Z2Vec;
+
+    fn add(self, rhs: Z2Vec) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl std::ops::BitXorAssign<&Z2Vec> for Z2Vec {
+    fn bitxor_assign(&mut self, rhs: &Z2Vec) {
+        self.xor_words(rhs);
+    }
+}
+
+impl std::ops::BitXor for &Z2Vec {
+    type Output = Z2Vec;
+
+    fn bitxor(self, rhs: &Z2Vec) -> Self::Output {
+        let mut result = self.clone();
+        result ^= rhs;
+        result
+    }
+}
+
+// The entrywise product
+impl std::ops::BitAnd for &Z2Vec {
+    type Output = Z2Vec;
+
+    fn bitand(self, rhs: &Z2Vec) -> Self::Output {
+        self.check_len(rhs);
+        let words = self
+            .words
+            .iter()
+            .zip(rhs.words.iter())
+            .map(|(a, b)| a & b)
+            .collect();
+        Z2Vec {
+            len: self.len,
+            words,
+        }
+    }
+}
+
+// The entries as a string of 0s and 1s, lowest index first
+impl std::fmt::Display for Z2Vec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        for value in self.iter() {
+            write!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_str(bits: &str) -> Z2Vec {
+        bits.chars().map(|c| Z2z::from(c == '1')).collect()
+    }
+
+    #[test]
+    fn get_set_and_display() {
+        let mut v = Z2Vec::zeros(70);
+        v.set(0, Z2z::One);
+        v.set(65, Z2z::One);
+        v.flip(3);
+        v.flip(0);
+        assert_eq!(v.get(3), Z2z::One);
+        assert_eq!(v.get(0), Z2z::Zero);
+        assert_eq!(v.words(), &[0b1000, 0b10]);
+        assert_eq!(v.weight(), 2);
+        assert_eq!(from_str("0110").to_string(), "0110");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn get_past_the_end_panics() {
+        Z2Vec::zeros(64).get(64);
+    }
+
+    #[test]
+    fn from_words_clears_the_tail() {
+        let v = Z2Vec::from_words(3, vec![u64::MAX, u64::MAX]);
+        assert_eq!(v, from_str("111"));
+        assert_eq!(v.words(), &[0b111]);
+    }
+
+    #[test]
+    fn add_and_dot() {
+        let a = from_str("1101");
+        let b = from_str("0111");
+        assert_eq!(&a + &b, from_str("1010"));
+        assert_eq!(&a ^ &b, &a + &b);
+        assert_eq!(&a & &b, from_str("0101"));
+        assert_eq!(a.dot(&b), Z2z::Zero);
+        assert_eq!(a.dot(&from_str("1000")), Z2z::One);
+        assert!((a.clone() + a).is_zero());
+    }
+
+    #[test]
+    fn first_one_crosses_words() {
+        let v = Z2Vec::unit(200, 130);
+        assert_eq!(v.first_one(0), Some(130));
+        assert_eq!(v.first_one(130), Some(130));
+        assert_eq!(v.first_one(131), None);
+        assert_eq!(Z2Vec::zeros(10).first_one(0), None);
+    }
+
+    #[test]
+    fn bits_slice_and_concat() {
+        let v: Z2Vec = (0..150).map(|i| Z2z::from(i % 3 == 0)).collect();
+        assert_eq!(v.bits(0, 4), 0b1001);
+        assert_eq!(v.bits(63, 3), 0b001);
+        assert_eq!(v.bits(147, 8), 0b001);
+        let (left, right) = (v.slice(0, 67), v.slice(67, 150));
+        assert_eq!(right.get(2), v.get(69));
+        assert_eq!(left.concat(&right), v);
+        assert_eq!(Z2Vec::zeros(0).concat(&v), v);
+        assert_eq!(v.slice(5, 5), Z2Vec::zeros(0));
+    }
+}